@@ -0,0 +1,227 @@
+use crate::card::{CardRank, Deck, Hand, HandRank, find_best_hand};
+use crate::equity::simulate_vs_random;
+use crate::game::{Action, Street, Table, TableConfig, TableError, TableView};
+use crate::preflop::PreflopTable;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+/// A player that decides what to do when it is their turn.
+pub trait Agent {
+    fn name(&self) -> String;
+
+    /// Pick one of `view.legal`. An illegal choice is treated as a check,
+    /// or a fold when checking is not possible.
+    fn act(&mut self, view: &TableView) -> Action;
+}
+
+fn check_or_call(view: &TableView) -> Action {
+    if view.legal.check { Action::Check } else { Action::Call }
+}
+
+fn check_or_fold(view: &TableView) -> Action {
+    if view.legal.check { Action::Check } else { Action::Fold }
+}
+
+// Bet or raise to about `target`, kept within the legal range.
+fn bet_or_raise(view: &TableView, target: u32) -> Action {
+    if let Some((min, max)) = view.legal.bet {
+        Action::Bet(target.clamp(min, max))
+    } else if let Some((min, max)) = view.legal.raise {
+        Action::Raise(target.clamp(min, max))
+    } else {
+        check_or_call(view)
+    }
+}
+
+/// Never folds and never raises.
+pub struct CallingStation;
+
+impl Agent for CallingStation {
+    fn name(&self) -> String {
+        String::from("Calling Station")
+    }
+
+    fn act(&mut self, view: &TableView) -> Action {
+        check_or_call(view)
+    }
+}
+
+// Points for the high card in the Chen formula.
+fn chen_points(rank: CardRank) -> f64 {
+    match rank {
+        CardRank::Ace => 10.0,
+        CardRank::King => 8.0,
+        CardRank::Queen => 7.0,
+        CardRank::Jack => 6.0,
+        r => (r.index() + 2) as f64 / 2.0,
+    }
+}
+
+/// Bill Chen's preflop score for two hole cards, from -1 (7-2 offsuit)
+/// to 20 (a pair of aces).
+pub fn chen_score(hole: &Hand) -> f64 {
+    assert_eq!( hole.count(), 2 );
+    let (a, b) = (hole.card(0), hole.card(1));
+    let (high, low) = if a.rank >= b.rank { (a.rank, b.rank) } else { (b.rank, a.rank) };
+    let mut score = chen_points(high);
+    if high == low {
+        return (score * 2.0).max(5.0);
+    }
+    if a.suite == b.suite {
+        score += 2.0;
+    }
+    let gap = high.index() - low.index() - 1;
+    score -= match gap {
+        0 => 0.0,
+        1 => 1.0,
+        2 => 2.0,
+        3 => 4.0,
+        _ => 5.0,
+    };
+    if gap <= 1 && high < CardRank::Queen {
+        score += 1.0;
+    }
+    score.ceil()
+}
+
+/// Plays few hands before the flop, chosen by `chen_score`, and bets
+/// them hard; afterwards bets strong made hands and gives up on weak ones.
+pub struct TightAggressive {
+    pub raise_score: f64,
+    pub call_score: f64,
+}
+
+impl Default for TightAggressive {
+    fn default() -> Self {
+        TightAggressive { raise_score: 10.0, call_score: 7.0 }
+    }
+}
+
+impl Agent for TightAggressive {
+    fn name(&self) -> String {
+        String::from("Tight Aggressive")
+    }
+
+    fn act(&mut self, view: &TableView) -> Action {
+        let current = view.bets.iter().copied().max().unwrap_or(0);
+        if view.street == Street::Preflop {
+            let score = chen_score(&view.hole);
+            if score >= self.raise_score {
+                return bet_or_raise(view, 3 * current.max(view.big_blind));
+            }
+            if score >= self.call_score && view.to_call() <= 4 * view.big_blind {
+                return check_or_call(view);
+            }
+            return check_or_fold(view);
+        }
+        let cards = [view.hole.cards(), view.board.cards()].concat();
+        let made = find_best_hand( &cards, false ).data.rank();
+        if made >= HandRank::TwoPair {
+            bet_or_raise(view, current + view.pot * 2 / 3)
+        } else if made == HandRank::Pair && view.to_call() <= view.pot / 2 {
+            check_or_call(view)
+        } else {
+            check_or_fold(view)
+        }
+    }
+}
+
+/// Calls whenever its estimated equity against random hands beats both
+/// a fixed threshold and the pot odds being offered.
+pub struct EquityCaller {
+    pub threshold: f64,
+    pub trials: u32,
+    rng: StdRng,
+    preflop: Option<PreflopTable>,
+}
+
+impl EquityCaller {
+    pub fn new(threshold: f64, trials: u32, seed: u64) -> EquityCaller {
+        EquityCaller { threshold, trials, rng: StdRng::seed_from_u64(seed), preflop: None }
+    }
+
+    /// Look preflop equity up in `table` instead of simulating it, for as
+    /// many opponents as the table covers.
+    pub fn with_preflop_table(mut self, table: PreflopTable) -> EquityCaller {
+        self.preflop = Some(table);
+        self
+    }
+
+    fn equity(&mut self, view: &TableView) -> f64 {
+        let looked_up = match &self.preflop {
+            Some(table) if view.board.count() == 0 => table.hand_equity( &view.hole, view.opponents() ),
+            _ => None,
+        };
+        if let Some(equity) = looked_up {
+            return equity;
+        }
+        match simulate_vs_random( &view.hole, &view.board, view.opponents(), self.trials, &mut self.rng ) {
+            Ok(res) => res.equity(0),
+            Err(_) => 0.0,
+        }
+    }
+}
+
+impl Agent for EquityCaller {
+    fn name(&self) -> String {
+        format!("Equity Caller ({:.0}%)", 100.0 * self.threshold)
+    }
+
+    fn act(&mut self, view: &TableView) -> Action {
+        if view.legal.check {
+            return Action::Check;
+        }
+        let equity = self.equity(view);
+        let to_call = view.to_call() as f64;
+        let pot_odds = to_call / (view.pot as f64 + to_call);
+        if equity >= self.threshold && equity >= pot_odds {
+            Action::Call
+        } else {
+            Action::Fold
+        }
+    }
+}
+
+/// Deal a hand from a freshly shuffled deck and ask each seat's agent for
+/// their actions until it is over.
+pub fn play_hand<R: Rng + ?Sized>(table: &mut Table, agents: &mut [Box<dyn Agent>],
+                                  rng: &mut R) -> Result<(), TableError> {
+    let mut deck = Deck::new();
+    deck.shuffle_with(rng);
+    table.start_hand(deck)?;
+    while let Some(view) = table.view() {
+        let action = agents[view.seat].act(&view);
+        if table.act( view.seat, action ).is_err() {
+            table.act( view.seat, check_or_fold(&view) ).unwrap();
+        }
+    }
+    Ok(())
+}
+
+/// Chips won or lost by each agent over a match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchResult {
+    pub hands: u32,
+    pub net: Vec<i64>,
+}
+
+/// Play `hands` deals between the agents, one per seat, with every stack
+/// reset to `stack` before each deal.
+pub fn run_match<R: Rng + ?Sized>(agents: &mut [Box<dyn Agent>], config: TableConfig, stack: u32,
+                                  hands: u32, rng: &mut R) -> Result<MatchResult, TableError> {
+    let mut table = Table::new(config, &vec![stack; agents.len()])?;
+    let mut net = vec![0i64; agents.len()];
+    for _ in 0..hands {
+        for seat in 0..agents.len() {
+            table.set_stack(seat, stack)?;
+        }
+        play_hand(&mut table, agents, rng)?;
+        for (n, s) in net.iter_mut().zip(table.seats()) {
+            *n += s.stack as i64 - stack as i64;
+        }
+    }
+    Ok(MatchResult { hands, net })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::*;
+    use crate::agent::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const CONFIG: TableConfig = TableConfig { small_blind: 5, big_blind: 10, ante: 0 };
+
+    fn chen(s: &str) -> f64 {
+        chen_score( &s.parse().unwrap() )
+    }
+
+    #[test]
+    fn chen_scores() {
+        assert_eq!( chen("A♠ A♥"), 20.0 );
+        assert_eq!( chen("A♠ K♠"), 12.0 );
+        assert_eq!( chen("K♥ K♦"), 16.0 );
+        assert_eq!( chen("2♣ 2♦"), 5.0 );
+        assert_eq!( chen("J♥ 10♥"), 9.0 );
+        assert_eq!( chen("5♠ 7♥"), 4.0 );
+        assert_eq!( chen("7♣ 2♦"), -1.0 );
+    }
+
+    #[test]
+    fn calling_stations_reach_showdown() {
+        let mut table = Table::new( CONFIG, &[1000, 1000, 1000] ).unwrap();
+        let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingStation), Box::new(CallingStation),
+                                                   Box::new(CallingStation)];
+        let mut rng = StdRng::seed_from_u64(7);
+        play_hand( &mut table, &mut agents, &mut rng ).unwrap();
+        assert!( !table.in_progress() );
+        assert_eq!( table.board().count(), 5 );
+        let stacks: Vec<u32> = table.seats().iter().map(|s| s.stack).collect();
+        assert_eq!( stacks.iter().sum::<u32>(), 3000 );
+        // everyone put in the big blind and nobody bet after
+        assert!( stacks.iter().all(|s| *s == 990 || *s > 1000) );
+    }
+
+    #[test]
+    fn match_conserves_chips() {
+        let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingStation),
+                                                   Box::new(TightAggressive::default()),
+                                                   Box::new(EquityCaller::new(0.5, 200, 3))];
+        let mut rng = StdRng::seed_from_u64(11);
+        let res = run_match( &mut agents, CONFIG, 1000, 200, &mut rng ).unwrap();
+        assert_eq!( res.hands, 200 );
+        assert_eq!( res.net.iter().sum::<i64>(), 0 );
+        assert!( res.net.iter().any(|n| *n != 0) );
+    }
+
+    #[test]
+    fn tight_aggressive_preflop() {
+        let mut table = Table::new( CONFIG, &[1000, 1000] ).unwrap();
+        // the button (small blind) is dealt A♠ and A♥
+        table.start_hand( "K♦ A♠ 7♣ A♥ 2♦ 3♦ 4♦ 5♦ 6♦".parse().unwrap() ).unwrap();
+        let view = table.view().unwrap();
+        let mut tag = TightAggressive::default();
+        assert!( matches!( tag.act(&view), Action::Raise(30) ) );
+    }
+
+    #[test]
+    fn equity_caller_folds_trash() {
+        let mut table = Table::new( CONFIG, &[1000, 1000] ).unwrap();
+        table.start_hand( "A♠ 7♣ A♥ 2♦ K♦ 3♦ 4♦ 5♦ 6♦".parse().unwrap() ).unwrap();
+        let view = table.view().unwrap();
+        let mut bot = EquityCaller::new(0.45, 500, 1);
+        assert_eq!( bot.act(&view), Action::Fold );
+        let mut bot = EquityCaller::new(0.3, 500, 1);
+        assert_eq!( bot.act(&view), Action::Call );
+    }
+
+    #[test]
+    fn equity_caller_uses_preflop_table() {
+        use crate::preflop::PreflopTable;
+        use crate::range::HandClass;
+
+        let mut table = Table::new( CONFIG, &[1000, 1000] ).unwrap();
+        table.start_hand( "A♠ 7♣ A♥ 2♦ K♦ 3♦ 4♦ 5♦ 6♦".parse().unwrap() ).unwrap();
+        let view = table.view().unwrap();
+        // a table that rates every hand highly makes the same trash a call
+        let csv: String = HandClass::all().iter().map(|c| format!("{},0.9\n", c)).collect();
+        let preflop: PreflopTable = format!("hand,vs1\n{}", csv).parse().unwrap();
+        let mut bot = EquityCaller::new(0.45, 500, 1).with_preflop_table(preflop);
+        assert_eq!( bot.act(&view), Action::Call );
+    }
+}
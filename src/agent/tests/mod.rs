@@ -0,0 +1 @@
+pub mod agent_tests;
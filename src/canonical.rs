@@ -0,0 +1,363 @@
+use crate::card::{Card, CardRank, CardSuite, Hand, binomial};
+use strum::IntoEnumIterator;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Error returned when indexing cards that are not a possible deal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexError {
+    WrongCount { count: usize, expected: usize },
+    DuplicateCard(Card),
+    NotInDeck(Card),
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IndexError::WrongCount { count, expected } =>
+                write!(f, "{} cards given, expected {}", count, expected),
+            IndexError::DuplicateCard(card) =>
+                write!(f, "card {} is given more than once", card),
+            IndexError::NotInDeck(card) =>
+                write!(f, "card {} is not in the deck", card),
+        }
+    }
+}
+
+impl std::error::Error for IndexError {}
+
+// Position of a set of small numbers, given as a bit mask, among all sets
+// of the same size in colexicographic order.
+fn colex_index(mut mask: u16) -> u64 {
+    let mut idx = 0;
+    let mut j = 1;
+    while mask != 0 {
+        idx += binomial(mask.trailing_zeros() as u64, j);
+        mask &= mask - 1;
+        j += 1;
+    }
+    idx
+}
+
+// The `k` increasing numbers at position `idx` in colexicographic order,
+// smallest first.
+fn colex_unindex(mut idx: u64, k: u64) -> Vec<u64> {
+    let mut v = vec![0; k as usize];
+    for j in (1..=k).rev() {
+        let mut p = j - 1;
+        while binomial(p + 1, j) <= idx {
+            p += 1;
+        }
+        idx -= binomial(p, j);
+        v[j as usize - 1] = p;
+    }
+    v
+}
+
+// Pack the bits of `mask` that are also in `within` down into consecutive
+// low bits, so ranks can be numbered among those still available.
+fn compress(mask: u16, within: u16) -> u16 {
+    let mut out = 0;
+    let mut bit = 0;
+    for r in 0..13 {
+        if within & (1 << r) != 0 {
+            if mask & (1 << r) != 0 {
+                out |= 1 << bit;
+            }
+            bit += 1;
+        }
+    }
+    out
+}
+
+// The inverse of `compress`.
+fn expand(packed: u16, within: u16) -> u16 {
+    let mut out = 0;
+    let mut bit = 0;
+    for r in 0..13 {
+        if within & (1 << r) != 0 {
+            if packed & (1 << bit) != 0 {
+                out |= 1 << r;
+            }
+            bit += 1;
+        }
+    }
+    out
+}
+
+// How many cards one suite has in each round.
+type Shape = Vec<u8>;
+
+// A group of suites with the same shape, which can be swapped for each
+// other without changing the hand.
+struct Group {
+    shape: Shape,
+    suites: usize,
+    // ways to deal one suite this shape, and a multiset of them to the group
+    histories: u64,
+    size: u64,
+}
+
+// One way of splitting each round's cards between the suites, as the
+// suites' shapes from largest to smallest.
+struct Config {
+    groups: Vec<Group>,
+    offset: u64,
+}
+
+/// Numbers hands that are the same up to swapping suites, such as A♠ K♠ and
+/// A♥ K♥, with a dense index from 0 up to the number of distinct hands. The
+/// cards are dealt in rounds of fixed size; for hold'em, the two hole cards
+/// then the flop, turn and river. Hole cards alone have 169 classes, with
+/// the flop 1,286,792.
+pub struct HandIndexer {
+    rounds: Vec<usize>,
+    configs: Vec<Config>,
+    lookup: HashMap<Vec<Shape>, usize>,
+    size: u64,
+}
+
+impl HandIndexer {
+    /// An indexer for hands dealt in rounds of the given sizes.
+    pub fn new(rounds: &[usize]) -> HandIndexer {
+        assert!( !rounds.is_empty() && rounds.iter().sum::<usize>() <= 52 );
+        let mut shapes: Vec<Vec<Shape>> = Vec::new();
+        let mut chosen = Vec::new();
+        let mut left: Vec<u8> = rounds.iter().map(|n| *n as u8).collect();
+        config_shapes(&all_shapes(rounds), 0, &mut chosen, &mut left, &mut shapes);
+
+        let mut indexer = HandIndexer { rounds: rounds.to_vec(), configs: Vec::new(), lookup: HashMap::new(), size: 0 };
+        for shapes in shapes {
+            let mut groups: Vec<Group> = Vec::new();
+            for shape in &shapes {
+                match groups.last_mut() {
+                    Some(g) if g.shape == *shape => g.suites += 1,
+                    _ => groups.push( Group { shape: shape.clone(), suites: 1, histories: shape_histories(shape), size: 0 } ),
+                }
+            }
+            for g in groups.iter_mut() {
+                g.size = binomial(g.histories + g.suites as u64 - 1, g.suites as u64);
+            }
+            let size: u64 = groups.iter().map(|g| g.size).product();
+            indexer.lookup.insert(shapes, indexer.configs.len());
+            indexer.configs.push( Config { groups, offset: indexer.size } );
+            indexer.size += size;
+        }
+        indexer
+    }
+
+    /// An indexer for hold'em hole cards and a board of 0, 3, 4 or 5 cards.
+    /// The board is one round, since the order it came in does not change
+    /// the hand; use `new(&[2, 3, 1])` to tell the turn from the flop.
+    pub fn holdem(board: usize) -> HandIndexer {
+        match board {
+            0 => HandIndexer::new(&[2]),
+            3..=5 => HandIndexer::new(&[2, board]),
+            _ => panic!("a hold'em board has 0, 3, 4 or 5 cards, not {}", board),
+        }
+    }
+
+    /// Number of distinct hands; every index is below it.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    // Each suite's cards, as a rank mask for each round.
+    fn histories(&self, cards: &[Card]) -> Result<Vec<Vec<u16>>, IndexError> {
+        let expected: usize = self.rounds.iter().sum();
+        if cards.len() != expected {
+            return Err(IndexError::WrongCount { count: cards.len(), expected });
+        }
+        let mut masks = vec![vec![0u16; self.rounds.len()]; 4];
+        let mut seen = [0u16; 4];
+        let mut i = 0;
+        for (r, n) in self.rounds.iter().enumerate() {
+            for c in &cards[i..i + n] {
+                if c.rank == CardRank::Joker {
+                    return Err(IndexError::NotInDeck(c.clone()));
+                }
+                let bit = 1 << c.rank.index();
+                if seen[c.suite.index()] & bit != 0 {
+                    return Err(IndexError::DuplicateCard(c.clone()));
+                }
+                seen[c.suite.index()] |= bit;
+                masks[c.suite.index()][r] |= bit;
+            }
+            i += n;
+        }
+        Ok(masks)
+    }
+
+    // The suites in canonical order, largest shape first and then the
+    // highest history within a shape, each with its shape and history index.
+    fn sorted(&self, cards: &[Card]) -> Result<Vec<(Shape, u64, Vec<u16>)>, IndexError> {
+        let mut suites: Vec<(Shape, u64, Vec<u16>)> = self.histories(cards)?.into_iter()
+            .map(|m| {
+                let shape: Shape = m.iter().map(|r| r.count_ones() as u8).collect();
+                (shape, history_index(&m), m)
+            })
+            .collect();
+        suites.sort_by(|a, b| (&b.0, b.1).cmp(&(&a.0, a.1)));
+        Ok(suites)
+    }
+
+    /// The index of a hand, with the cards given round by round: for
+    /// hold'em, the hole cards then the board in the order dealt.
+    pub fn index(&self, cards: &[Card]) -> Result<u64, IndexError> {
+        let suites = self.sorted(cards)?;
+        let shapes: Vec<Shape> = suites.iter().map(|s| s.0.clone()).collect();
+        let config = &self.configs[self.lookup[&shapes]];
+
+        let mut idx = 0;
+        let mut radix = 1;
+        let mut s = 0;
+        for g in &config.groups {
+            // the group's history indices as a multiset, smallest first
+            let mut members: Vec<u64> = suites[s..s + g.suites].iter().map(|x| x.1).collect();
+            members.reverse();
+            let multiset: u64 = members.iter().enumerate()
+                .map(|(j, h)| binomial(h + j as u64, j as u64 + 1))
+                .sum();
+            idx += multiset * radix;
+            radix *= g.size;
+            s += g.suites;
+        }
+        Ok(config.offset + idx)
+    }
+
+    /// The canonical hand for an index: the same cards for every hand that
+    /// differs only by suites, round by round, best ranks first. None if the
+    /// index is out of range.
+    pub fn unindex(&self, index: u64) -> Option<Hand> {
+        if index >= self.size {
+            return None;
+        }
+        let c = self.configs.partition_point(|c| c.offset <= index) - 1;
+        let config = &self.configs[c];
+        let mut idx = index - config.offset;
+
+        let mut masks: Vec<Vec<u16>> = Vec::new();
+        for g in &config.groups {
+            let multiset = idx % g.size;
+            idx /= g.size;
+            let k = g.suites as u64;
+            let members = colex_unindex(multiset, k);
+            // largest history first, as in `sorted`
+            for (j, b) in members.iter().enumerate().rev() {
+                masks.push( history_masks(b - j as u64, &g.shape) );
+            }
+        }
+        Some(self.hand_of(&masks))
+    }
+
+    // Deal out the suites' rank masks as cards, round by round, giving the
+    // suites out in their usual order.
+    fn hand_of(&self, masks: &[Vec<u16>]) -> Hand {
+        let suites: Vec<CardSuite> = CardSuite::iter().collect();
+        let ranks: Vec<CardRank> = CardRank::iter().collect();
+        let mut h = Hand::new();
+        for r in 0..self.rounds.len() {
+            let mut cards: Vec<Card> = Vec::new();
+            for (s, m) in masks.iter().enumerate() {
+                for (i, rank) in ranks.iter().enumerate() {
+                    if m[r] & (1 << i) != 0 {
+                        cards.push( Card { rank: *rank, suite: suites[s].clone() } );
+                    }
+                }
+            }
+            cards.sort_by_key(|c| (std::cmp::Reverse(c.rank), c.suite.index()));
+            for c in cards {
+                h.add(c);
+            }
+        }
+        h
+    }
+
+    /// The canonical hand with the same index as the given cards.
+    pub fn canonical(&self, cards: &[Card]) -> Result<Hand, IndexError> {
+        let masks: Vec<Vec<u16>> = self.sorted(cards)?.into_iter().map(|s| s.2).collect();
+        Ok(self.hand_of(&masks))
+    }
+}
+
+// Every shape a suite could have: up to a round's size in each round.
+fn all_shapes(rounds: &[usize]) -> Vec<Shape> {
+    let mut shapes: Vec<Shape> = vec![Vec::new()];
+    for n in rounds {
+        shapes = shapes.iter()
+            .flat_map(|s| (0..=*n as u8).map(move |k| [s.clone(), vec![k]].concat()))
+            .collect();
+    }
+    shapes.sort_by(|a, b| b.cmp(a));
+    shapes
+}
+
+// Choose shapes for the four suites, largest first, so that each round's
+// cards are all dealt, collecting every way of doing so.
+fn config_shapes(shapes: &[Shape], from: usize, chosen: &mut Vec<Shape>, left: &mut [u8], out: &mut Vec<Vec<Shape>>) {
+    if chosen.len() == 4 {
+        if left.iter().all(|n| *n == 0) {
+            out.push( chosen.clone() );
+        }
+        return;
+    }
+    for (i, shape) in shapes.iter().enumerate().skip(from) {
+        if shape.iter().zip(left.iter()).any(|(k, n)| k > n) {
+            continue;
+        }
+        for (n, k) in left.iter_mut().zip(shape) {
+            *n -= k;
+        }
+        chosen.push( shape.clone() );
+        config_shapes(shapes, i, chosen, left, out);
+        chosen.pop();
+        for (n, k) in left.iter_mut().zip(shape) {
+            *n += k;
+        }
+    }
+}
+
+// Ways to deal one suite the given number of ranks in each round.
+fn shape_histories(shape: &Shape) -> u64 {
+    let mut used = 0;
+    let mut ways = 1;
+    for k in shape {
+        ways *= binomial(13 - used, *k as u64);
+        used += *k as u64;
+    }
+    ways
+}
+
+// Number one suite's ranks in each round, each round's ranks counted
+// among those the suite has not had yet.
+fn history_index(masks: &[u16]) -> u64 {
+    let mut used: u16 = 0;
+    let mut idx = 0;
+    let mut radix = 1;
+    for m in masks {
+        let avail = !used & 0x1fff;
+        idx += colex_index( compress(*m, avail) ) * radix;
+        radix *= binomial(avail.count_ones() as u64, m.count_ones() as u64);
+        used |= m;
+    }
+    idx
+}
+
+// The inverse of `history_index` for a suite of the given shape.
+fn history_masks(mut idx: u64, shape: &Shape) -> Vec<u16> {
+    let mut used: u16 = 0;
+    let mut masks = Vec::new();
+    for k in shape {
+        let avail = !used & 0x1fff;
+        let ways = binomial(avail.count_ones() as u64, *k as u64);
+        let packed = colex_unindex(idx % ways, *k as u64).iter().fold(0, |m, p| m | 1 << p);
+        let m = expand( packed, avail );
+        idx /= ways;
+        masks.push(m);
+        used |= m;
+    }
+    masks
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::canonical::*;
+    use crate::range::HandClass;
+    use crate::card::tests::support::hand;
+    use rand::Rng;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::collections::HashMap;
+    use strum::IntoEnumIterator;
+
+    // The same cards with every suite moved along by `shift`.
+    fn rotate(cards: &[Card], shift: usize) -> Vec<Card> {
+        let suites: Vec<CardSuite> = CardSuite::iter().collect();
+        cards.iter().map(|c| Card { rank: c.rank, suite: suites[(c.suite.index() + shift) % 4].clone() }).collect()
+    }
+
+    #[test]
+    fn class_counts() {
+        assert_eq!( HandIndexer::holdem(0).size(), 169 );
+        assert_eq!( HandIndexer::holdem(3).size(), 1_286_792 );
+        assert_eq!( HandIndexer::holdem(4).size(), 13_960_050 );
+        assert_eq!( HandIndexer::holdem(5).size(), 123_156_254 );
+        // the turn as a round of its own
+        assert_eq!( HandIndexer::new(&[2, 3, 1]).size(), 55_190_538 );
+    }
+
+    #[test]
+    fn preflop_matches_hand_classes() {
+        let indexer = HandIndexer::holdem(0);
+        let mut classes: HashMap<u64, HandClass> = HashMap::new();
+        for class in HandClass::all() {
+            for h in class.combos() {
+                let i = indexer.index(h.cards()).unwrap();
+                assert_eq!( *classes.entry(i).or_insert(class), class );
+                let canonical = indexer.unindex(i).unwrap();
+                assert_eq!( HandClass::of(&canonical), Some(class) );
+                assert_eq!( indexer.canonical(h.cards()).unwrap(), canonical );
+            }
+        }
+        assert_eq!( classes.len(), 169 );
+        assert_eq!( indexer.unindex(169), None );
+
+        assert_eq!( indexer.canonical(hand("Kd Ad").cards()).unwrap(), hand("Ah Kh") );
+        assert_eq!( indexer.canonical(hand("Kd Ac").cards()).unwrap(), hand("Ah Ks") );
+        assert_eq!( indexer.canonical(hand("7c 7d").cards()).unwrap(), hand("7h 7s") );
+    }
+
+    #[test]
+    fn every_index_used_once() {
+        // a small game dealt in full: two cards then one
+        let indexer = HandIndexer::new(&[2, 1]);
+        let deck = Deck::new();
+        let cards = deck.cards();
+        let mut seen = vec![false; indexer.size() as usize];
+        for i in 0..52 {
+            for j in i + 1..52 {
+                for (k, c) in cards.iter().enumerate() {
+                    if k == i || k == j {
+                        continue;
+                    }
+                    let hand = [cards[i].clone(), cards[j].clone(), c.clone()];
+                    seen[indexer.index(&hand).unwrap() as usize] = true;
+                }
+            }
+        }
+        assert!( seen.iter().all(|s| *s) );
+        for i in 0..indexer.size() {
+            let h = indexer.unindex(i).unwrap();
+            assert_eq!( indexer.index(h.cards()), Ok(i) );
+        }
+    }
+
+    #[test]
+    fn flop_round_trip() {
+        let indexer = HandIndexer::holdem(3);
+        let mut rng = StdRng::seed_from_u64(23);
+        for _ in 0..2000 {
+            let i = rng.gen_range(0..indexer.size());
+            let h = indexer.unindex(i).unwrap();
+            assert_eq!( h.cards().len(), 5 );
+            assert_eq!( indexer.index(h.cards()), Ok(i) );
+        }
+        for seed in 0..500 {
+            let mut deck = Deck::new_seeded(seed);
+            let cards: Vec<Card> = (0..5).map(|_| deck.draw().unwrap()).collect();
+            let i = indexer.index(&cards).unwrap();
+            for shift in 1..4 {
+                assert_eq!( indexer.index(&rotate(&cards, shift)), Ok(i) );
+            }
+            // the order within a round does not matter, but across rounds it does
+            let mut swapped = cards.clone();
+            swapped.swap(2, 4);
+            assert_eq!( indexer.index(&swapped), Ok(i) );
+            assert_eq!( indexer.unindex(i), Some(indexer.canonical(&cards).unwrap()) );
+        }
+        let a = indexer.index(hand("Ah Kh Qh 7d 2c").cards()).unwrap();
+        let b = indexer.index(hand("Qh Kh Ah 7d 2c").cards()).unwrap();
+        assert!( a != b );
+        assert_eq!( indexer.canonical(hand("2c 7d Ah Kh Qh").cards()).unwrap(), hand("7h 2s Ad Kd Qd") );
+    }
+
+    #[test]
+    fn index_errors() {
+        let indexer = HandIndexer::holdem(3);
+        assert_eq!( indexer.index(hand("Ah Kh").cards()),
+            Err(IndexError::WrongCount { count: 2, expected: 5 }) );
+        let ace = Card { rank: CardRank::Ace, suite: CardSuite::Hearts };
+        let mut cards = hand("Ah Kh Qh 2c").cards().to_vec();
+        cards.push( ace.clone() );
+        assert_eq!( indexer.index(&cards), Err(IndexError::DuplicateCard(ace)) );
+        let joker = Card { rank: CardRank::Joker, suite: CardSuite::Hearts };
+        cards[4] = joker.clone();
+        assert_eq!( indexer.index(&cards), Err(IndexError::NotInDeck(joker)) );
+    }
+}
@@ -1,10 +1,13 @@
 use strum_macros::EnumIter;
 use strum::IntoEnumIterator;
-use rand::thread_rng;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, EnumIter, PartialEq, Eq)]
+#[derive(Debug, Clone, EnumIter, PartialEq, Eq, Hash)]
 pub enum CardSuite {
     Hearts,
     Spades,
@@ -13,6 +16,31 @@ pub enum CardSuite {
 }
 
 impl CardSuite {
+    pub fn index(&self) -> usize {
+        self.clone() as usize
+    }
+
+    /// Position in bridge order, lowest first: clubs, diamonds, hearts,
+    /// spades. Used where suits break ties, such as the stud bring-in.
+    pub fn bridge_order(&self) -> usize {
+        match self {
+            CardSuite::Clubs    => 0,
+            CardSuite::Diamonds => 1,
+            CardSuite::Hearts   => 2,
+            CardSuite::Spades   => 3,
+        }
+    }
+
+    /// The suite's letter in text notation: h, s, d or c.
+    pub fn letter(&self) -> char {
+        match self {
+            CardSuite::Hearts   => 'h',
+            CardSuite::Spades   => 's',
+            CardSuite::Diamonds => 'd',
+            CardSuite::Clubs    => 'c',
+        }
+    }
+
     fn name(&self) -> String {
         match self {
             CardSuite::Hearts   => String::from("\u{2665}"),
@@ -23,7 +51,7 @@ impl CardSuite {
     }
 }
 
-#[derive(Debug, Copy, Clone, EnumIter, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, EnumIter, PartialOrd, Ord, PartialEq, Eq, Hash)]
 pub enum CardRank {
     Two,
     Three,
@@ -38,13 +66,29 @@ pub enum CardRank {
     Queen,
     King,
     Ace,
+    // always wild; left out of iteration so decks have none unless added
+    #[strum(disabled)]
+    Joker,
 }
 
 impl CardRank {
-    fn index(&self) -> usize {
+    pub fn index(&self) -> usize {
         *self as usize
     }
 
+    /// The rank's letter in text notation, with T for ten.
+    pub fn letter(&self) -> char {
+        match self {
+            CardRank::Ten => 'T',
+            r => r.name().chars().next().unwrap(),
+        }
+    }
+
+    /// The rank written as `letter` gives it, in either case.
+    pub fn from_letter(c: char) -> Option<CardRank> {
+        parse_rank(c)
+    }
+
     fn name(&self) -> String {
         match self {
             CardRank::Two   => String::from("2"),
@@ -60,11 +104,12 @@ impl CardRank {
             CardRank::Queen => String::from("Q"),
             CardRank::King  => String::from("K"),
             CardRank::Ace   => String::from("A"),
+            CardRank::Joker => String::from("X"),
         }
     }
 }
 
-#[derive(Debug, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
 pub enum HandRank {
     HighCard,
     Pair,
@@ -75,11 +120,14 @@ pub enum HandRank {
     FullHouse,
     FourOfAKind,
     StraightFlush,
+    // only possible with wild cards
+    FiveOfAKind,
 }
 
 impl HandRank {
     pub fn name(&self) -> String {
         match self {
+            HandRank::FiveOfAKind => String::from("Five of a Kind"),
             HandRank::StraightFlush => String::from("Straight Flush"),
             HandRank::FourOfAKind => String::from("Four of a Kind"),
             HandRank::FullHouse => String::from("Full House"),
@@ -93,7 +141,38 @@ impl HandRank {
     }
 }
 
-#[derive(Clone, Eq)]
+/// Rules for ranking 5 card hands that differ between games.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum HandRules {
+    #[default]
+    Standard,
+    // 36 card deck: a flush beats a full house and A-6-7-8-9 is a straight
+    ShortDeck,
+}
+
+impl HandRules {
+    /// Position of a category in the order of hand strength, higher is better.
+    pub fn strength(&self, rank: HandRank) -> u8 {
+        match (self, rank) {
+            (HandRules::ShortDeck, HandRank::Flush) => HandRank::FullHouse as u8,
+            (HandRules::ShortDeck, HandRank::FullHouse) => HandRank::Flush as u8,
+            (_, r) => r as u8,
+        }
+    }
+
+    // Top card of the straight where the ace plays low.
+    fn wheel_top(&self) -> CardRank {
+        match self {
+            HandRules::Standard => CardRank::Five,
+            HandRules::ShortDeck => CardRank::Nine,
+        }
+    }
+}
+
+/// A playing card. Two cards are equal only if both rank and suite match;
+/// use `Card::cmp_rank` to order cards by rank alone. A joker's suite only
+/// tells apart the red joker (hearts) and the black joker (spades).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Card {
     pub rank: CardRank,
     pub suite: CardSuite,
@@ -101,32 +180,165 @@ pub struct Card {
 
 impl Card {
     pub fn name(&self) -> String {
-        self.rank.name() + &self.suite.name()
+        match self.rank {
+            CardRank::Joker => String::from("Joker"),
+            _ => self.rank.name() + &self.suite.name(),
+        }
+    }
+
+    /// Compare by rank only, ignoring suite.
+    pub fn cmp_rank(&self, other: &Card) -> Ordering {
+        self.rank.cmp(&other.rank)
     }
 }
 
-impl PartialOrd for Card {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
     }
 }
 
-impl Ord for Card {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.rank.cmp(&other.rank)
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cards = parse_cards(s)?;
+        match cards.len() {
+            0 => Err(ParseCardError::Empty),
+            1 => Ok(cards.remove(0).0),
+            _ => {
+                let (_, token, position) = cards.remove(1);
+                Err(ParseCardError::TrailingInput { token, position })
+            }
+        }
     }
 }
 
-impl PartialEq for Card {
-    fn eq(&self, other: &Self) -> bool {
-        self.rank == other.rank
+/// Error returned when parsing cards from text notation.
+/// Positions are character offsets into the parsed string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    Empty,
+    InvalidRank { token: String, position: usize },
+    InvalidSuit { token: String, position: usize },
+    TrailingInput { token: String, position: usize },
+    DuplicateCard { token: String, position: usize },
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCardError::Empty =>
+                write!(f, "no card given"),
+            ParseCardError::InvalidRank { token, position } =>
+                write!(f, "invalid rank in '{}' at position {}", token, position),
+            ParseCardError::InvalidSuit { token, position } =>
+                write!(f, "invalid suit in '{}' at position {}", token, position),
+            ParseCardError::TrailingInput { token, position } =>
+                write!(f, "unexpected '{}' at position {}", token, position),
+            ParseCardError::DuplicateCard { token, position } =>
+                write!(f, "duplicate card '{}' at position {}", token, position),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || c == ','
+}
+
+fn parse_rank(c: char) -> Option<CardRank> {
+    match c.to_ascii_uppercase() {
+        '2' => Some(CardRank::Two),
+        '3' => Some(CardRank::Three),
+        '4' => Some(CardRank::Four),
+        '5' => Some(CardRank::Five),
+        '6' => Some(CardRank::Six),
+        '7' => Some(CardRank::Seven),
+        '8' => Some(CardRank::Eight),
+        '9' => Some(CardRank::Nine),
+        'T' => Some(CardRank::Ten),
+        'J' => Some(CardRank::Jack),
+        'Q' => Some(CardRank::Queen),
+        'K' => Some(CardRank::King),
+        'A' => Some(CardRank::Ace),
+        'X' => Some(CardRank::Joker),
+        _ => None,
+    }
+}
+
+fn parse_suite(c: char) -> Option<CardSuite> {
+    match c {
+        'h' | 'H' | '\u{2665}' | '\u{2661}' => Some(CardSuite::Hearts),
+        's' | 'S' | '\u{2660}' | '\u{2664}' => Some(CardSuite::Spades),
+        'd' | 'D' | '\u{2666}' | '\u{2662}' => Some(CardSuite::Diamonds),
+        'c' | 'C' | '\u{2663}' | '\u{2667}' => Some(CardSuite::Clubs),
+        _ => None,
     }
 }
 
+// Parse a list of cards such as "As Kd", "Ah,10c", "T\u{2660}" or "AsKd".
+// Jokers are written "Xh" and "Xs".
+// Each card is returned with its source token and character position.
+fn parse_cards(s: &str) -> Result<Vec<(Card, String, usize)>, ParseCardError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut cards = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if is_separator(chars[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let token = || -> String {
+            chars[start..].iter().take_while(|c| !is_separator(**c)).collect()
+        };
+        let rank = if chars[i] == '1' && chars.get(i+1) == Some(&'0') {
+            i += 2;
+            CardRank::Ten
+        } else {
+            match parse_rank(chars[i]) {
+                Some(r) => {
+                    i += 1;
+                    r
+                }
+                None => return Err(ParseCardError::InvalidRank { token: token(), position: start }),
+            }
+        };
+        let suite = match chars.get(i).and_then(|c| parse_suite(*c)) {
+            Some(s) => s,
+            None => return Err(ParseCardError::InvalidSuit { token: token(), position: start }),
+        };
+        i += 1;
+        cards.push( (Card { rank, suite }, chars[start..i].iter().collect(), start) );
+    }
+    Ok(cards)
+}
+
+// Parse a list of cards, rejecting any card given more than once.
+fn parse_distinct_cards(s: &str) -> Result<Vec<Card>, ParseCardError> {
+    let mut cards = Vec::<Card>::new();
+    for (card, token, position) in parse_cards(s)? {
+        if cards.contains(&card) {
+            return Err(ParseCardError::DuplicateCard { token, position });
+        }
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+#[derive(Clone)]
 pub struct Deck {
     cards: Vec<Card>,
 }
 
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Deck {
     pub fn new() -> Deck {
         let mut v : Vec<Card> = Vec::new();
@@ -141,24 +353,95 @@ impl Deck {
         Deck { cards: v }
     }
 
+    /// A deck of the given cards, the last of them drawn first.
+    pub fn from_cards(cards: Vec<Card>) -> Deck {
+        Deck { cards }
+    }
+
+    /// Add up to two jokers to the deck, the red one first.
+    pub fn add_jokers(&mut self, count: usize) {
+        assert!( count <= 2 );
+        for suite in [CardSuite::Hearts, CardSuite::Spades].into_iter().take(count) {
+            self.cards.push( Card { rank: CardRank::Joker, suite } );
+        }
+    }
+
+    /// The 36 card deck for short-deck games, without the 2s through 5s.
+    pub fn new_short() -> Deck {
+        let mut d = Deck::new();
+        d.cards.retain(|c| c.rank >= CardRank::Six);
+        d
+    }
+
+    /// A full deck shuffled from the given seed; the same seed always
+    /// gives the same card order.
+    pub fn new_seeded(seed: u64) -> Deck {
+        let mut d = Deck::new();
+        d.shuffle_with(&mut StdRng::seed_from_u64(seed));
+        d
+    }
+
     pub fn shuffle(&mut self) {
-        self.cards.shuffle(&mut thread_rng());
+        self.shuffle_with(&mut thread_rng());
+    }
+
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
     }
 
     pub fn draw(&mut self) -> Option<Card> {
         self.cards.pop()
     }
 
+    /// Take a specific card out of the deck, returning false if it was not there.
+    pub fn remove(&mut self, card: &Card) -> bool {
+        match self.cards.iter().position(|c| c == card) {
+            Some(i) => {
+                self.cards.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn count(&self) -> usize {
         self.cards.len()
     }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+// Decks are written in the order they will be drawn, top card first.
+impl fmt::Display for Deck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self.cards.iter().rev().map(|c| c.name()).collect();
+        write!(f, "{}", names.join(" "))
+    }
 }
 
-#[derive(Clone, PartialEq, Eq)]
+impl FromStr for Deck {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cards = parse_distinct_cards(s)?;
+        cards.reverse();
+        Ok(Deck { cards })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Hand {
     cards: Vec<Card>,
 }
 
+impl Default for Hand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Hand {
     pub fn new() -> Hand {
         Hand { cards: Vec::<Card>::new() }
@@ -186,21 +469,27 @@ impl Hand {
         self.cards[idx].clone()
     }
 
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
     pub fn remove(&mut self, idx: usize) -> Card {
         self.cards.remove(idx)
     }
+}
 
-    pub fn to_string(&self) -> String {
-        let mut s = String::new();
-        let mut i = 0;
-        while i < self.count() {
-            s.push_str( &self.cards[i].name() );
-            if i+1 < self.count() {
-                s.push_str( " " );
-            }
-            i = i+1;
-        }
-        s
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self.cards.iter().map(|c| c.name()).collect();
+        write!(f, "{}", names.join(" "))
+    }
+}
+
+impl FromStr for Hand {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Hand { cards: parse_distinct_cards(s)? })
     }
 }
 
@@ -210,7 +499,7 @@ pub fn compare_sorted_hands( h1: &Hand, h2: &Hand ) -> Ordering {
 
     let mut i = 0;
     while i < h1.count() {
-        res = h1.card(i).cmp(&h2.card(i));
+        res = h1.card(i).cmp_rank(&h2.card(i));
         if res != Ordering::Equal {
             break;
         }
@@ -224,13 +513,22 @@ pub struct HandWithData {
     hand: Hand,
     kicker: Hand,
     rank: HandRank,
+    rules: HandRules,
+    // each wild card and the card it stands in for
+    wild: Vec<(Card, Card)>,
 }
 
 impl HandWithData {
     pub fn from_hand(h: &Hand) -> HandWithData {
+        HandWithData::from_hand_with(h, HandRules::Standard)
+    }
+
+    /// Rank a 5 card hand under the given rules. Only hands ranked under
+    /// the same rules should be compared.
+    pub fn from_hand_with(h: &Hand, rules: HandRules) -> HandWithData {
         assert!(h.count() == 5);
         let mut s = h.clone();
-        s.cards.sort_by(|a, b| b.rank.index().cmp(&a.rank.index()));
+        s.cards.sort_by_key(|c| std::cmp::Reverse(c.rank.index()));
         // is there a flush?
         let f = 
             s.card(0).suite == s.card(1).suite &&
@@ -243,12 +541,13 @@ impl HandWithData {
             s.card(1).rank.index() == s.card(2).rank.index() + 1 &&
             s.card(2).rank.index() == s.card(3).rank.index() + 1 &&
             s.card(3).rank.index() == s.card(4).rank.index() + 1;
-        t = t || (   // wheel
+        let wheel =
             s.card(0).rank == CardRank::Ace &&
-            s.card(1).rank == CardRank::Five &&
-            s.card(2).rank == CardRank::Four &&
-            s.card(3).rank == CardRank::Three &&
-            s.card(4).rank == CardRank::Two );
+            s.card(1).rank == rules.wheel_top() &&
+            s.card(1).rank.index() == s.card(2).rank.index() + 1 &&
+            s.card(2).rank.index() == s.card(3).rank.index() + 1 &&
+            s.card(3).rank.index() == s.card(4).rank.index() + 1;
+        t = t || wheel;
         // accumulate kicker cards as appropriate
         let mut k = Hand::new();
         // compute rank
@@ -256,91 +555,90 @@ impl HandWithData {
 
         if t {
             // for straights, kicker is the high card
-            if s.card(0).rank == CardRank::Ace && s.card(1).rank == CardRank::Five {
+            if wheel {
                 k.add( s.card(1) );
             } else {
                 k.add( s.card(0) );
             }
         }
+        if s.card(0).rank == s.card(4).rank {
+            r = HandRank::FiveOfAKind;
+        }
         if f && t {
             r = HandRank::StraightFlush;
         }
         if r == HandRank::HighCard {
-            if s.card(0) == s.card(1) && s.card(0) == s.card(2) && s.card(0) == s.card(3) {
+            if s.card(0).rank == s.card(1).rank && s.card(0).rank == s.card(2).rank && s.card(0).rank == s.card(3).rank {
                 r = HandRank::FourOfAKind;
                 k.add( s.remove(4) );
-            } else if s.card(1) == s.card(2) && s.card(1) == s.card(3) && s.card(1) == s.card(4) {
+            } else if s.card(1).rank == s.card(2).rank && s.card(1).rank == s.card(3).rank && s.card(1).rank == s.card(4).rank {
                 r = HandRank::FourOfAKind;
                 k.add( s.remove(0) );
             }
         }
         if r == HandRank::HighCard {
-            if (s.card(0) == s.card(1) && s.card(0) == s.card(2)) &&
-               (s.card(3) == s.card(4)) {
+            if (s.card(0).rank == s.card(1).rank && s.card(0).rank == s.card(2).rank) &&
+               (s.card(3).rank == s.card(4).rank) {
                r = HandRank::FullHouse;
                k.add( s.remove(3) );
                k.add( s.remove(3) );
-            } else if (s.card(0) == s.card(1)) &&
-               (s.card(2) == s.card(3) && s.card(2) == s.card(4)) {
+            } else if (s.card(0).rank == s.card(1).rank) &&
+               (s.card(2).rank == s.card(3).rank && s.card(2).rank == s.card(4).rank) {
                r = HandRank::FullHouse;
                k.add( s.remove(0) );
                k.add( s.remove(0) );
             }
         }
-        if r == HandRank::HighCard {
-            if f {
-                r = HandRank::Flush;
-            }
+        if r == HandRank::HighCard && f {
+            r = HandRank::Flush;
         }
-        if r == HandRank::HighCard {
-            if t {
-                r = HandRank::Straight;
-            }
+        if r == HandRank::HighCard && t {
+            r = HandRank::Straight;
         }
         if r == HandRank::HighCard {
-            if s.card(0) == s.card(1) && s.card(0) == s.card(2) {
+            if s.card(0).rank == s.card(1).rank && s.card(0).rank == s.card(2).rank {
                 r = HandRank::ThreeOfAKind;
                 k.add( s.remove(3) );
                 k.add( s.remove(3) );
-            } else if s.card(1) == s.card(2) && s.card(1) == s.card(3) {
+            } else if s.card(1).rank == s.card(2).rank && s.card(1).rank == s.card(3).rank {
                 r = HandRank::ThreeOfAKind;
                 k.add( s.remove(0) );
                 k.add( s.remove(3) );
-            } else if s.card(2) == s.card(3) && s.card(2) == s.card(4) {
+            } else if s.card(2).rank == s.card(3).rank && s.card(2).rank == s.card(4).rank {
                 r = HandRank::ThreeOfAKind;
                 k.add( s.remove(0) );
                 k.add( s.remove(0) );
             }
         }
         if r == HandRank::HighCard {
-            if s.card(0) == s.card(1) && s.card(2) == s.card(3) {
+            if s.card(0).rank == s.card(1).rank && s.card(2).rank == s.card(3).rank {
                 r = HandRank::TwoPair;
                 k.add( s.remove(4) );
-            } else if s.card(0) == s.card(1) && s.card(3) == s.card(4) {
+            } else if s.card(0).rank == s.card(1).rank && s.card(3).rank == s.card(4).rank {
                 r = HandRank::TwoPair;
                 k.add( s.remove(2) );
-            } else if s.card(1) == s.card(2) && s.card(3) == s.card(4) {
+            } else if s.card(1).rank == s.card(2).rank && s.card(3).rank == s.card(4).rank {
                 r = HandRank::TwoPair;
                 k.add( s.remove(0) );
             }
         }
         if r == HandRank::HighCard {
-            if s.card(0) == s.card(1) {
+            if s.card(0).rank == s.card(1).rank {
                 r = HandRank::Pair;
                 k.add( s.remove(2) );
                 k.add( s.remove(2) );
                 k.add( s.remove(2) );
-            } else if s.card(1) == s.card(2) {
+            } else if s.card(1).rank == s.card(2).rank {
                 r = HandRank::Pair;
                 k.add( s.remove(0) );
                 k.add( s.remove(2) );
                 k.add( s.remove(2) );
-            } else if s.card(2) == s.card(3) {
+            } else if s.card(2).rank == s.card(3).rank {
                 r = HandRank::Pair;
                 k.add( s.remove(0) );
                 k.add( s.remove(0) );
                 k.add( s.remove(2) );
-            } else if s.card(3) == s.card(4) {
+            } else if s.card(3).rank == s.card(4).rank {
                 r = HandRank::Pair;
                 k.add( s.remove(0) );
                 k.add( s.remove(0) );
@@ -352,12 +650,134 @@ impl HandWithData {
             s = Hand::new();
         }
 
-        HandWithData{ hand: s, kicker: k, rank: r }
+        HandWithData{ hand: s, kicker: k, rank: r, rules, wild: Vec::new() }
+    }
+
+    /// Rank a 5 card hand in which some cards may be wild, with each wild
+    /// card standing in for whatever card makes the strongest hand.
+    pub fn from_wild_hand(h: &Hand, wild: &WildCards, rules: HandRules) -> HandWithData {
+        assert!(h.count() == 5);
+        if !h.cards.iter().any(|c| wild.is_wild(c)) {
+            return HandWithData::from_hand_with(h, rules);
+        }
+        let (wilds, naturals): (Vec<Card>, Vec<Card>) = h.cards.iter().cloned().partition(|c| wild.is_wild(c));
+
+        // a flush is only possible when the natural cards share a suite
+        let flush_suite = match naturals.first() {
+            None => Some(CardSuite::Spades),
+            Some(c) if naturals.iter().all(|n| n.suite == c.suite) => Some(c.suite.clone()),
+            Some(_) => None,
+        };
+
+        let ranks: Vec<CardRank> = CardRank::iter().collect();
+        let mut best: Option<HandWithData> = None;
+        // wild cards are interchangeable, so try each multiset of ranks once
+        for c in combinations(ranks.len() + wilds.len() - 1, wilds.len()) {
+            let chosen: Vec<CardRank> = c.iter().enumerate().map(|(i, x)| ranks[x - i]).collect();
+            for suited in [false, true] {
+                if suited && flush_suite.is_none() {
+                    continue;
+                }
+                let Some(stand_ins) = stand_ins(&naturals, &chosen, flush_suite.as_ref(), suited) else {
+                    continue;
+                };
+                let mut cards = naturals.clone();
+                cards.extend( stand_ins.iter().cloned() );
+                let mut hwd = HandWithData::from_hand_with(&Hand { cards }, rules);
+                hwd.wild = wilds.iter().cloned().zip(stand_ins).collect();
+                if best.as_ref().is_none_or(|b| hwd > *b) {
+                    best = Some(hwd);
+                }
+            }
+        }
+        best.unwrap()
+    }
+
+    pub fn rank(&self) -> HandRank {
+        self.rank
+    }
+
+    /// Each wild card in the hand with the card it stood in for.
+    pub fn wild(&self) -> &[(Card, Card)] {
+        &self.wild
+    }
+}
+
+// Cards of the chosen ranks for wild cards to stand in for. Suited, they
+// all take `flush_suite`, and there are none when one of those cards is
+// already in the hand; otherwise they avoid it, so as not to make a flush,
+// and avoid repeating cards already in the hand where they can.
+fn stand_ins(naturals: &[Card], ranks: &[CardRank], flush_suite: Option<&CardSuite>, suited: bool) -> Option<Vec<Card>> {
+    let mut used = naturals.to_vec();
+    for rank in ranks {
+        let card = match flush_suite {
+            Some(s) if suited => {
+                let card = Card { rank: *rank, suite: s.clone() };
+                if used.contains(&card) {
+                    return None;
+                }
+                card
+            }
+            _ => {
+                let suites: Vec<CardSuite> = CardSuite::iter().filter(|s| Some(s) != flush_suite).collect();
+                let fresh = suites.iter().find(|s| !used.contains(&Card { rank: *rank, suite: (*s).clone() }));
+                Card { rank: *rank, suite: fresh.unwrap_or(&suites[0]).clone() }
+            }
+        };
+        used.push(card);
+    }
+    Some(used.split_off(naturals.len()))
+}
+
+/// Which cards are wild. Jokers always are; `ranks` makes every card of
+/// those ranks wild too, as in deuces wild.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WildCards {
+    pub ranks: Vec<CardRank>,
+}
+
+impl WildCards {
+    /// Jokers and every card of the given rank.
+    pub fn rank(rank: CardRank) -> WildCards {
+        WildCards { ranks: vec![rank] }
     }
 
-    pub fn to_string(&self) -> String {
+    pub fn is_wild(&self, card: &Card) -> bool {
+        card.rank == CardRank::Joker || self.ranks.contains(&card.rank)
+    }
+}
+
+// Wild ranks are written as a list of ranks, such as "2" or "2,J".
+impl FromStr for WildCards {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ranks = Vec::new();
+        let mut position = 0;
+        for token in s.split(is_separator) {
+            let rank = match token {
+                "10" => Some(CardRank::Ten),
+                t if t.chars().count() == 1 => t.chars().next().and_then(parse_rank),
+                _ => None,
+            };
+            match rank {
+                Some(r) => ranks.push(r),
+                None if !token.is_empty() =>
+                    return Err(ParseCardError::InvalidRank { token: token.to_string(), position }),
+                None => (),
+            }
+            position += token.chars().count() + 1;
+        }
+        Ok(WildCards { ranks })
+    }
+}
+
+impl fmt::Display for HandWithData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let details = 
             match self.rank {
+                HandRank::FiveOfAKind =>
+                    format!("Rank: {}", self.hand.card(0).name()),
                 HandRank::StraightFlush =>
                     format!("High: {}", self.kicker.card(0).name()),
                 HandRank::FourOfAKind =>
@@ -365,19 +785,24 @@ impl HandWithData {
                 HandRank::FullHouse =>
                     format!("Rank: {}, Kicker: {}", self.hand.card(0).name(), self.kicker.card(0).name()),
                 HandRank::Flush =>
-                    format!("Rank: {}", self.hand.to_string()),
+                    format!("Rank: {}", self.hand),
                 HandRank::Straight => 
                     format!("High: {}", self.kicker.card(0).name()),
                 HandRank::ThreeOfAKind =>
-                    format!("Rank: {}, Kickers: {}", self.hand.card(0).name(), self.kicker.to_string()),
+                    format!("Rank: {}, Kickers: {}", self.hand.card(0).name(), self.kicker),
                 HandRank::TwoPair =>
-                    format!("High Rank: {}, Low Rank: {}, Kicker: {}", self.hand.card(0).name(), self.hand.card(2).name(), self.kicker.to_string()),
+                    format!("High Rank: {}, Low Rank: {}, Kicker: {}", self.hand.card(0).name(), self.hand.card(2).name(), self.kicker),
                 HandRank::Pair =>
-                    format!("Rank: {}, Kickers: {}", self.hand.card(0).name(), self.kicker.to_string()),
+                    format!("Rank: {}, Kickers: {}", self.hand.card(0).name(), self.kicker),
                 HandRank::HighCard =>
-                    format!("Kickers: {}", self.kicker.to_string()),
+                    format!("Kickers: {}", self.kicker),
             };
-        format!(" {}, {}", self.rank.name(), details)
+        write!(f, " {}, {}", self.rank.name(), details)?;
+        if !self.wild.is_empty() {
+            let subs: Vec<String> = self.wild.iter().map(|(w, c)| format!("{} as {}", w, c)).collect();
+            write!(f, " ({})", subs.join(", "))?;
+        }
+        Ok(())
     }
 }
 
@@ -389,43 +814,45 @@ impl PartialOrd for HandWithData {
 
 impl Ord for HandWithData {
     fn cmp(&self, other: &Self) -> Ordering {
-        let mut res = self.rank.cmp(&other.rank);
+        let mut res = self.rules.strength(self.rank).cmp(&other.rules.strength(other.rank));
         if res == Ordering::Equal {
             res = match self.rank {
+                HandRank::FiveOfAKind =>
+                    self.hand.card(0).cmp_rank(&other.hand.card(0)),
                 HandRank::StraightFlush =>
-                    self.kicker.card(0).cmp(&other.kicker.card(0)),
+                    self.kicker.card(0).cmp_rank(&other.kicker.card(0)),
                 HandRank::FourOfAKind =>
-                    self.hand.card(0).cmp(&other.hand.card(0)),
+                    self.hand.card(0).cmp_rank(&other.hand.card(0)),
                 HandRank::FullHouse => {
-                    let mut r = self.hand.card(0).cmp(&other.hand.card(0));
+                    let mut r = self.hand.card(0).cmp_rank(&other.hand.card(0));
                     if r == Ordering::Equal {
-                        r = self.kicker.card(0).cmp(&other.kicker.card(0));
+                        r = self.kicker.card(0).cmp_rank(&other.kicker.card(0));
                     }
                     r
                 }
                 HandRank::Flush =>
                     compare_sorted_hands( &self.hand, &other.hand ),
                 HandRank::Straight =>
-                    self.kicker.card(0).cmp(&other.kicker.card(0)),
+                    self.kicker.card(0).cmp_rank(&other.kicker.card(0)),
                 HandRank::ThreeOfAKind => {
-                    let mut r = self.hand.card(0).cmp(&other.hand.card(0));
+                    let mut r = self.hand.card(0).cmp_rank(&other.hand.card(0));
                     if r == Ordering::Equal {
                         r = compare_sorted_hands( &self.kicker, &other.kicker );
                     }
                     r
                 }
                 HandRank::TwoPair => {
-                    let mut r = self.hand.card(0).cmp(&other.hand.card(0));
+                    let mut r = self.hand.card(0).cmp_rank(&other.hand.card(0));
                     if r == Ordering::Equal {
-                        r = self.hand.card(2).cmp(&other.hand.card(2));
+                        r = self.hand.card(2).cmp_rank(&other.hand.card(2));
                         if r == Ordering::Equal {
-                            r = self.kicker.card(0).cmp(&other.kicker.card(0));
+                            r = self.kicker.card(0).cmp_rank(&other.kicker.card(0));
                         }
                     }
                     r
                 }
                 HandRank::Pair => {
-                    let mut r = self.hand.card(0).cmp(&other.hand.card(0));
+                    let mut r = self.hand.card(0).cmp_rank(&other.hand.card(0));
                     if r == Ordering::Equal {
                         r = compare_sorted_hands( &self.kicker, &other.kicker );
                     }
@@ -445,101 +872,343 @@ impl PartialEq for HandWithData {
     }
 }
 
+/// All ways of choosing `k` of `n` indices, each in ascending order.
+pub fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut res = Vec::new();
+    if k > n {
+        return res;
+    }
+    let mut idx: Vec<usize> = (0..k).collect();
+    loop {
+        res.push( idx.clone() );
+        // advance the rightmost index that still has room to move
+        let mut i = k;
+        while i > 0 && idx[i-1] == n - k + i - 1 {
+            i -= 1;
+        }
+        if i == 0 {
+            break;
+        }
+        idx[i-1] += 1;
+        for j in i..k {
+            idx[j] = idx[j-1] + 1;
+        }
+    }
+    res
+}
+
+/// Number of ways of choosing `k` of `n` things.
+pub(crate) fn binomial(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1u128, |acc, i| acc * (n - i) as u128 / (i + 1) as u128) as u64
+}
+
+/// Indices of the players holding the best of `shown`, more than one when
+/// they tie.
+pub fn winners<T: Ord>(shown: &[T]) -> Vec<usize> {
+    let best = shown.iter().max().unwrap();
+    (0..shown.len()).filter(|i| shown[*i] == *best).collect()
+}
+
 // Form all 5 card candidates
-fn form_all_candidates(h1: Hand, h2: Hand) -> Vec::<Hand> {
-    assert_eq!( h1.count(), 2 );
-    assert_eq!( h2.count(), 5 );
-
-    let mut hands = Vec::<Hand>::new();
-    // 0 own cards
-    hands.push(
-        h2.clone()
-    );
-    // 1 own cards
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h2.card(0), h2.card(1), h2.card(2), h2.card(3) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h2.card(0), h2.card(1), h2.card(2), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h2.card(0), h2.card(1), h2.card(3), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h2.card(0), h2.card(2), h2.card(3), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h2.card(1), h2.card(2), h2.card(3), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(1), h2.card(0), h2.card(1), h2.card(2), h2.card(3) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(1), h2.card(0), h2.card(1), h2.card(2), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(1), h2.card(0), h2.card(1), h2.card(3), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(1), h2.card(0), h2.card(2), h2.card(3), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(1), h2.card(1), h2.card(2), h2.card(3), h2.card(4) )
-    );
-    // 2 own cards
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(0), h2.card(1), h2.card(2) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(0), h2.card(1), h2.card(3) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(0), h2.card(1), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(0), h2.card(2), h2.card(3) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(0), h2.card(2), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(0), h2.card(3), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(1), h2.card(2), h2.card(3) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(1), h2.card(2), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(1), h2.card(3), h2.card(4) )
-    );
-    hands.push( 
-        Hand::new_from_cards( h1.card(0), h1.card(1), h2.card(2), h2.card(3), h2.card(4) )
-    );
+fn form_all_candidates(cards: &[Card]) -> Vec::<Hand> {
+    assert!( cards.len() >= 5 );
 
-    hands
+    combinations(cards.len(), 5).iter()
+        .map(|c| Hand { cards: c.iter().map(|i| cards[*i].clone()).collect() })
+        .collect()
 }
 
-pub fn find_best_hand(h1: Hand, h2: Hand, verbose: bool) -> HandWithData {
-    let hands = form_all_candidates(h1, h2);
-    let mut best = HandWithData::from_hand(&hands[0]);
+/// The best 5 card hand among a larger set of cards, with the cards used.
+pub struct BestHand {
+    pub cards: Hand,
+    pub data: HandWithData,
+}
+
+// The best of the candidate hands, printing each one when verbose.
+fn best_of(hands: Vec<Hand>, rules: HandRules, wild: &WildCards, verbose: bool) -> BestHand {
+    let mut best = BestHand { data: HandWithData::from_wild_hand(&hands[0], wild, rules), cards: hands[0].clone() };
 
     if verbose {
         println!("Candidates:");
     }
     for h in hands {
-        let hwd = HandWithData::from_hand(&h);
+        let hwd = HandWithData::from_wild_hand(&h, wild, rules);
         if verbose {
-            println!(" {} -> {}", h.to_string(), hwd.to_string());
+            println!(" {} -> {}", h, hwd);
         }
-        if hwd > best {
-            best = hwd;
+        if hwd > best.data {
+            best = BestHand { cards: h, data: hwd };
         }
     }
     best
 }
 
+/// Find the best 5 card hand from 5 or more cards, such as hole cards
+/// plus a full or partial board.
+pub fn find_best_hand(cards: &[Card], verbose: bool) -> BestHand {
+    find_best_hand_with(cards, HandRules::Standard, verbose)
+}
+
+/// Find the best 5 card hand from 5 or more cards under the given rules.
+pub fn find_best_hand_with(cards: &[Card], rules: HandRules, verbose: bool) -> BestHand {
+    find_best_wild_hand(cards, &WildCards::default(), rules, verbose)
+}
+
+/// Find the best 5 card hand from 5 or more cards, some of which may be wild.
+pub fn find_best_wild_hand(cards: &[Card], wild: &WildCards, rules: HandRules, verbose: bool) -> BestHand {
+    best_of(form_all_candidates(cards), rules, wild, verbose)
+}
+
+// Form the 5 card candidates with exactly two hole cards and three board cards
+fn form_omaha_candidates(hole: &[Card], board: &[Card]) -> Vec<Hand> {
+    assert!( hole.len() >= 2 && board.len() >= 3 );
+
+    let mut hands = Vec::new();
+    for h in combinations(hole.len(), 2) {
+        for b in combinations(board.len(), 3) {
+            let cards = h.iter().map(|i| hole[*i].clone())
+                .chain(b.iter().map(|i| board[*i].clone()))
+                .collect();
+            hands.push( Hand { cards } );
+        }
+    }
+    hands
+}
+
+/// Find the best Omaha hand, which must use exactly two of the hole cards
+/// and three cards from a board of at least three.
+pub fn find_best_omaha_hand(hole: &[Card], board: &[Card], verbose: bool) -> BestHand {
+    find_best_wild_omaha_hand(hole, board, &WildCards::default(), verbose)
+}
+
+/// Find the best Omaha hand when some cards may be wild.
+pub fn find_best_wild_omaha_hand(hole: &[Card], board: &[Card], wild: &WildCards, verbose: bool) -> BestHand {
+    best_of(form_omaha_candidates(hole, board), HandRules::Standard, wild, verbose)
+}
+
+/// Highest card allowed in a qualifying low in eight-or-better games.
+pub const EIGHT_OR_BETTER: u8 = 8;
+
+/// Value of a rank in an ace-to-five low, where the ace is one.
+pub fn low_value(rank: CardRank) -> u8 {
+    match rank {
+        CardRank::Ace => 1,
+        r => r.index() as u8 + 2,
+    }
+}
+
+/// The rank name for a card value, from 2 up to 14, with 1 also an ace.
+pub fn value_name(value: u8) -> String {
+    match value {
+        1 => CardRank::Ace.name(),
+        v => CardRank::iter().nth(v as usize - 2).unwrap().name(),
+    }
+}
+
+/// An ace-to-five low: five cards of different ranks, aces counting as one
+/// and straights and flushes ignored. The lower hand is the better low and
+/// compares greater, as with `HandWithData`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LowHand {
+    // card values from highest to lowest
+    values: [u8; 5],
+}
+
+impl LowHand {
+    /// The low made by 5 cards, or None if any two share a rank.
+    pub fn from_hand(hand: &Hand) -> Option<LowHand> {
+        assert!( hand.count() == 5 );
+        let mut values: Vec<u8> = hand.cards.iter().map(|c| low_value(c.rank)).collect();
+        values.sort_by_key(|v| std::cmp::Reverse(*v));
+        if values.windows(2).any(|w| w[0] == w[1]) {
+            return None;
+        }
+        Some(LowHand { values: [values[0], values[1], values[2], values[3], values[4]] })
+    }
+
+    /// Value of the highest card, 1 for an ace up to 13 for a king.
+    pub fn high(&self) -> u8 {
+        self.values[0]
+    }
+
+    /// Whether the highest card is no more than `limit`.
+    pub fn qualifies(&self, limit: u8) -> bool {
+        self.high() <= limit
+    }
+}
+
+impl Ord for LowHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.values.cmp(&self.values)
+    }
+}
+
+impl PartialOrd for LowHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for LowHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self.values.iter().map(|v| value_name(*v)).collect();
+        write!(f, "{}", names.join("-"))
+    }
+}
+
+/// The best qualifying low among a larger set of cards, with the cards used.
+pub struct BestLow {
+    pub cards: Hand,
+    pub data: LowHand,
+}
+
+// The best of the candidate lows no higher than `limit`, if any qualify.
+fn best_low_of(hands: Vec<Hand>, limit: u8) -> Option<BestLow> {
+    let mut best: Option<BestLow> = None;
+    for h in hands {
+        if let Some(low) = LowHand::from_hand(&h) {
+            if low.qualifies(limit) && best.as_ref().is_none_or(|b| low > b.data) {
+                best = Some(BestLow { cards: h, data: low });
+            }
+        }
+    }
+    best
+}
+
+/// Find the best low no higher than `limit` from 5 or more cards.
+pub fn find_best_low(cards: &[Card], limit: u8) -> Option<BestLow> {
+    best_low_of(form_all_candidates(cards), limit)
+}
+
+/// Find the best Omaha low no higher than `limit`, using exactly two hole
+/// cards and three board cards.
+pub fn find_best_omaha_low(hole: &[Card], board: &[Card], limit: u8) -> Option<BestLow> {
+    best_low_of(form_omaha_candidates(hole, board), limit)
+}
+
+/// The best high hand and, separately, the best qualifying low a player
+/// can make in a split game. Both may use the same cards.
+pub struct BestHiLo {
+    pub high: BestHand,
+    pub low: Option<BestLow>,
+}
+
+/// The games that can be dealt and compared.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Variant {
+    #[default]
+    Holdem,
+    Omaha,
+    Omaha5,
+    Omaha6,
+    OmahaHiLo,
+    ShortDeck,
+}
+
+impl Variant {
+    pub fn name(&self) -> String {
+        match self {
+            Variant::Holdem    => String::from("Texas Hold'em"),
+            Variant::Omaha     => String::from("Omaha"),
+            Variant::Omaha5    => String::from("5-card Omaha"),
+            Variant::Omaha6    => String::from("6-card Omaha"),
+            Variant::OmahaHiLo => String::from("Omaha Hi-Lo"),
+            Variant::ShortDeck => String::from("Short Deck Hold'em"),
+        }
+    }
+
+    /// Number of hole cards dealt to each player.
+    pub fn hole_cards(&self) -> usize {
+        match self {
+            Variant::Holdem    => 2,
+            Variant::Omaha     => 4,
+            Variant::Omaha5    => 5,
+            Variant::Omaha6    => 6,
+            Variant::OmahaHiLo => 4,
+            Variant::ShortDeck => 2,
+        }
+    }
+
+    /// A fresh, unshuffled deck for this game.
+    pub fn deck(&self) -> Deck {
+        match self {
+            Variant::ShortDeck => Deck::new_short(),
+            _ => Deck::new(),
+        }
+    }
+
+    /// Whether the pot is split between the best high and the best
+    /// eight-or-better low.
+    pub fn hi_lo(&self) -> bool {
+        *self == Variant::OmahaHiLo
+    }
+
+    /// The best hand a player can make from their hole cards and a board
+    /// of at least three cards, under this game's rules.
+    pub fn best_hand(&self, hole: &[Card], board: &[Card], verbose: bool) -> BestHand {
+        self.best_wild_hand(hole, board, &WildCards::default(), verbose)
+    }
+
+    /// The best hand when some cards may be wild. Wild cards only count
+    /// towards the high hand, never the low.
+    pub fn best_wild_hand(&self, hole: &[Card], board: &[Card], wild: &WildCards, verbose: bool) -> BestHand {
+        match self {
+            Variant::Holdem => find_best_wild_hand( &[hole, board].concat(), wild, HandRules::Standard, verbose ),
+            Variant::ShortDeck => find_best_wild_hand( &[hole, board].concat(), wild, HandRules::ShortDeck, verbose ),
+            _ => find_best_wild_omaha_hand( hole, board, wild, verbose ),
+        }
+    }
+
+    /// The best qualifying low, always None in games without one.
+    pub fn best_low(&self, hole: &[Card], board: &[Card]) -> Option<BestLow> {
+        match self {
+            Variant::OmahaHiLo => find_best_omaha_low( hole, board, EIGHT_OR_BETTER ),
+            _ => None,
+        }
+    }
+
+    /// The best high and low hands, found independently.
+    pub fn best_hi_lo(&self, hole: &[Card], board: &[Card], verbose: bool) -> BestHiLo {
+        BestHiLo { high: self.best_hand(hole, board, verbose), low: self.best_low(hole, board) }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVariantError(pub String);
+
+impl fmt::Display for ParseVariantError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown game '{}', expected holdem, short-deck, omaha, omaha5, omaha6 or omaha-hilo", self.0)
+    }
+}
+
+impl std::error::Error for ParseVariantError {}
+
+impl FromStr for Variant {
+    type Err = ParseVariantError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "holdem" => Ok(Variant::Holdem),
+            "short-deck" | "shortdeck" | "6+" => Ok(Variant::ShortDeck),
+            "omaha" | "omaha4" | "plo" => Ok(Variant::Omaha),
+            "omaha5" | "plo5" => Ok(Variant::Omaha5),
+            "omaha6" | "plo6" => Ok(Variant::Omaha6),
+            "omaha-hilo" | "omaha8" | "plo8" => Ok(Variant::OmahaHiLo),
+            _ => Err(ParseVariantError(s.to_string())),
+        }
+    }
+}
+
 #[cfg(test)]
-mod tests;
+pub mod tests;
 
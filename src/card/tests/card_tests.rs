@@ -21,11 +21,40 @@ mod tests {
 
         assert!( c1 != c2 );
         assert!( c2 != c3 );
-        assert!( c1 == c3 );
+        assert!( c1 != c3 );
+        assert!( c1 == c1.clone() );
+
+        assert!( c1.cmp_rank(&c3) == Ordering::Equal );
+        assert!( c1.cmp_rank(&c2) == Ordering::Less );
+        assert!( c2.cmp_rank(&c3) == Ordering::Greater );
 
         assert_eq!( c1.name(), String::from("7♣") );
     }
 
+    #[test]
+    fn card_identity() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        assert!( set.insert( "Ah".parse::<Card>().unwrap() ) );
+        assert!( set.insert( "As".parse::<Card>().unwrap() ) );
+        assert!( !set.insert( "Ah".parse::<Card>().unwrap() ) );
+        assert_eq!( set.len(), 2 );
+
+        let mut d = Deck::new();
+        let mut seen = HashSet::new();
+        while let Some(c) = d.draw() {
+            assert!( seen.insert(c) );
+        }
+        assert_eq!( seen.len(), 52 );
+
+        // hands with the same ranks but different suites are different hands
+        let h1: Hand = "Ah Kd".parse().unwrap();
+        let h2: Hand = "As Kd".parse().unwrap();
+        assert!( h1 != h2 );
+        assert!( compare_sorted_hands( &h1, &h2 ) == Ordering::Equal );
+    }
+
     #[test]
     fn deck_sanity() {
         let mut d = Deck::new();
@@ -37,6 +66,40 @@ mod tests {
         assert_eq!( s.count(), 52 );
     }
 
+    #[test]
+    fn deck_remove() {
+        let mut d = Deck::new();
+        let c: Card = "Qh".parse().unwrap();
+        assert!( d.remove(&c) );
+        assert_eq!( d.count(), 51 );
+        assert!( !d.remove(&c) );
+        assert_eq!( d.count(), 51 );
+        while let Some(x) = d.draw() {
+            assert!( x != c );
+        }
+    }
+
+    #[test]
+    fn deck_seeded() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        assert_eq!( Deck::new_seeded(42).to_string(), Deck::new_seeded(42).to_string() );
+        assert!( Deck::new_seeded(42).to_string() != Deck::new_seeded(43).to_string() );
+        assert!( Deck::new_seeded(42).to_string() != Deck::new().to_string() );
+
+        let mut r1 = StdRng::seed_from_u64(7);
+        let mut r2 = StdRng::seed_from_u64(7);
+        let mut d1 = Deck::new();
+        let mut d2 = Deck::new();
+        for _ in 0..3 {
+            d1.shuffle_with(&mut r1);
+            d2.shuffle_with(&mut r2);
+            assert_eq!( d1.to_string(), d2.to_string() );
+        }
+        assert_eq!( d1.count(), 52 );
+    }
+
     #[test]
     fn hand_sanity() {
         let mut h = Hand::new();
@@ -609,7 +672,317 @@ mod tests {
         d.add( Card{ rank: CardRank::Four, suite: CardSuite::Spades } );
         d.add( Card{ rank: CardRank::Five, suite: CardSuite::Spades } );
 
-        let c = form_all_candidates(h, d);
+        let cards = [h.cards(), d.cards()].concat();
+        let c = form_all_candidates( &cards );
         assert_eq!( c.len(), 21 );
+        assert_eq!( form_all_candidates( &cards[1..] ).len(), 6 );
+        assert_eq!( form_all_candidates( &cards[2..] ).len(), 1 );
+        assert!( c.iter().all(|h| h.count() == 5) );
+    }
+
+    #[test]
+    fn combinations_sanity() {
+        assert_eq!( combinations(7, 5).len(), 21 );
+        assert_eq!( combinations(4, 2), vec![vec![0, 1], vec![0, 2], vec![0, 3],
+                                           vec![1, 2], vec![1, 3], vec![2, 3]] );
+        assert_eq!( combinations(3, 3), vec![vec![0, 1, 2]] );
+        assert_eq!( combinations(3, 0), vec![Vec::<usize>::new()] );
+        assert!( combinations(2, 3).is_empty() );
+        assert_eq!( combinations(52, 2).len(), 1326 );
+    }
+
+    #[test]
+    fn find_best_hand_sanity() {
+        // flop: hole cards plus three board cards
+        let cards = "7c 7s 7d Ks 2h".parse::<Hand>().unwrap();
+        let best = find_best_hand( cards.cards(), false );
+        assert!( best.data.rank() == HandRank::ThreeOfAKind );
+        assert_eq!( best.cards.count(), 5 );
+
+        // turn: the best five of six cards, reporting which were used
+        let cards = "As 2d Ks Qs Js 10s".parse::<Hand>().unwrap();
+        let best = find_best_hand( cards.cards(), false );
+        assert!( best.data.rank() == HandRank::StraightFlush );
+        assert!( !best.cards.cards().contains(&"2d".parse().unwrap()) );
+
+        // river
+        let cards = "7c 7s Ac 2s 3s 4s 5d".parse::<Hand>().unwrap();
+        let best = find_best_hand( cards.cards(), false );
+        assert!( best.data.rank() == HandRank::Straight );
+        assert!( best.cards.cards().contains(&"Ac".parse().unwrap()) );
+        assert!( !best.cards.cards().contains(&"7c".parse().unwrap()) );
+        assert!( !best.cards.cards().contains(&"7s".parse().unwrap()) );
+
+        // more cards than a hold'em river still work
+        let cards = "7c 7s Ac 2s 3s 4s 5d 8h".parse::<Hand>().unwrap();
+        assert!( find_best_hand( cards.cards(), false ).data.rank() == HandRank::Straight );
+    }
+
+    #[test]
+    fn parse_card_sanity() {
+        let c: Card = "As".parse().unwrap();
+        assert!( c.rank == CardRank::Ace && c.suite == CardSuite::Spades );
+        let c: Card = "10c".parse().unwrap();
+        assert!( c.rank == CardRank::Ten && c.suite == CardSuite::Clubs );
+        let c: Card = "T\u{2660}".parse().unwrap();
+        assert!( c.rank == CardRank::Ten && c.suite == CardSuite::Spades );
+        let c: Card = " kH ".parse().unwrap();
+        assert!( c.rank == CardRank::King && c.suite == CardSuite::Hearts );
+        let c: Card = "2\u{2662}".parse().unwrap();
+        assert!( c.rank == CardRank::Two && c.suite == CardSuite::Diamonds );
+    }
+
+    #[test]
+    fn parse_hand_sanity() {
+        let h: Hand = "As Kd".parse().unwrap();
+        assert_eq!( h.count(), 2 );
+        assert!( h.card(1).rank == CardRank::King && h.card(1).suite == CardSuite::Diamonds );
+
+        let h: Hand = "Ah,10c, 9\u{2663}".parse().unwrap();
+        assert_eq!( h.count(), 3 );
+        assert!( h.card(1).rank == CardRank::Ten && h.card(1).suite == CardSuite::Clubs );
+
+        let h: Hand = "AsKdQh".parse().unwrap();
+        assert_eq!( h.count(), 3 );
+
+        let h: Hand = "".parse().unwrap();
+        assert_eq!( h.count(), 0 );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!( "".parse::<Card>().err(), Some(ParseCardError::Empty) );
+        assert_eq!( "Zs".parse::<Card>().err(),
+            Some(ParseCardError::InvalidRank { token: String::from("Zs"), position: 0 }) );
+        assert_eq!( "As Kx".parse::<Hand>().err(),
+            Some(ParseCardError::InvalidSuit { token: String::from("Kx"), position: 3 }) );
+        assert_eq!( "As, 10".parse::<Hand>().err(),
+            Some(ParseCardError::InvalidSuit { token: String::from("10"), position: 4 }) );
+        assert_eq!( "AsKd".parse::<Card>().err(),
+            Some(ParseCardError::TrailingInput { token: String::from("Kd"), position: 2 }) );
+        assert_eq!( "As Kd as".parse::<Hand>().err(),
+            Some(ParseCardError::DuplicateCard { token: String::from("as"), position: 6 }) );
+        assert_eq!( "\u{2665}A".parse::<Card>().err(),
+            Some(ParseCardError::InvalidRank { token: String::from("\u{2665}A"), position: 0 }) );
+    }
+
+    #[test]
+    fn display_roundtrip() {
+        let c = Card{ rank: CardRank::Ten, suite: CardSuite::Hearts };
+        assert_eq!( c.to_string(), String::from("10\u{2665}") );
+        let p: Card = c.to_string().parse().unwrap();
+        assert!( p.rank == c.rank && p.suite == c.suite );
+
+        let h: Hand = "Qs 10d 2c".parse().unwrap();
+        assert_eq!( h.to_string(), String::from("Q\u{2660} 10\u{2666} 2\u{2663}") );
+        assert!( h.to_string().parse::<Hand>().unwrap() == h );
+
+        let mut d: Deck = "As Kd".parse().unwrap();
+        assert_eq!( d.count(), 2 );
+        assert_eq!( d.to_string(), String::from("A\u{2660} K\u{2666}") );
+        assert_eq!( d.draw().unwrap().name(), String::from("A\u{2660}") );
+
+        let d = Deck::new();
+        let p: Deck = d.to_string().parse().unwrap();
+        assert_eq!( p.to_string(), d.to_string() );
+    }
+
+    fn omaha(hole: &str, board: &str) -> BestHand {
+        let hole: Hand = hole.parse().unwrap();
+        let board: Hand = board.parse().unwrap();
+        find_best_omaha_hand( hole.cards(), board.cards(), false )
+    }
+
+    #[test]
+    fn omaha_uses_two_hole_cards() {
+        // one heart in hand does not make a flush with four on the board
+        let best = omaha("Ah Kc Qd Js", "2h 5h 8h 9h Tc");
+        assert_eq!( best.data.rank(), HandRank::Straight );
+        // a board straight does not play without two hole cards to go with it
+        let best = omaha("2c 2d 3h 3s", "9h Tc Jd Qs Kh");
+        assert_eq!( best.data.rank(), HandRank::Pair );
+        // four aces in hand are only a pair
+        let best = omaha("As Ah Ad Ac", "2h 5c 8d Js Kh");
+        assert_eq!( best.data.rank(), HandRank::Pair );
+        // trips on the board make a full house only with a hole pair
+        let best = omaha("7c 7d Kc Qd", "9h 9c 9d 2s 3h");
+        assert_eq!( best.data.rank(), HandRank::FullHouse );
+
+        let hole: Hand = "7c 7d Kc Qd".parse().unwrap();
+        assert_eq!( best.cards.cards().iter().filter(|c| hole.cards().contains(c)).count(), 2 );
+    }
+
+    #[test]
+    fn omaha_bigger_hands() {
+        // the sixth hole card completes the only flush
+        let best = omaha("Ah 2c 3d 4s 5c Kh", "7h 8h 9h Jc Qd");
+        assert_eq!( best.data.rank(), HandRank::Flush );
+        let best = omaha("Ah 2c 3d 4s 5c", "7h 8h 9h Jc Qd");
+        assert_eq!( best.data.rank(), HandRank::HighCard );
+        // a flop is enough to make a hand
+        let best = omaha("Ah Ad Kc Qs", "As 7c 2d");
+        assert_eq!( best.data.rank(), HandRank::ThreeOfAKind );
+    }
+
+    #[test]
+    fn variants() {
+        assert_eq!( "omaha".parse::<Variant>(), Ok(Variant::Omaha) );
+        assert_eq!( "Holdem".parse::<Variant>(), Ok(Variant::Holdem) );
+        assert_eq!( "omaha6".parse::<Variant>(), Ok(Variant::Omaha6) );
+        assert!( "stud".parse::<Variant>().is_err() );
+        assert_eq!( Variant::Omaha5.hole_cards(), 5 );
+
+        let hole: Hand = "Ah Kh".parse().unwrap();
+        let board: Hand = "2h 5h 8h 9c Tc".parse().unwrap();
+        assert_eq!( Variant::Holdem.best_hand( hole.cards(), board.cards(), false ).data.rank(), HandRank::Flush );
+    }
+
+    fn low(s: &str) -> Option<LowHand> {
+        LowHand::from_hand( &s.parse().unwrap() )
+    }
+
+    #[test]
+    fn low_hands() {
+        // the wheel is the best low, straight and flush notwithstanding
+        let wheel = low("5h 4h 3h 2h Ah").unwrap();
+        assert_eq!( wheel.to_string(), "5-4-3-2-A" );
+        assert!( wheel > low("6c 4d 3h 2s As").unwrap() );
+        // compared from the top card down
+        assert!( low("8c 5d 4h 3s 2s").unwrap() > low("8c 6d 3h 2s As").unwrap() );
+        assert!( low("7c 6d 5h 4s 3s").unwrap() > low("8c 4d 3h 2s As").unwrap() );
+        assert_eq!( low("8c 5d 4h 3s 2s"), low("8d 5h 4c 3d 2h") );
+        // pairs do not make a low
+        assert!( low("As Ad 2c 3c 4c").is_none() );
+
+        assert!( low("8c 7d 6h 5s 4s").unwrap().qualifies(EIGHT_OR_BETTER) );
+        assert!( !low("9c 4d 3h 2s As").unwrap().qualifies(EIGHT_OR_BETTER) );
+        assert_eq!( low("Kc Qd 3h 2s As").unwrap().to_string(), "K-Q-3-2-A" );
+    }
+
+    #[test]
+    fn best_lows() {
+        let cards: Hand = "As 2d 9c 3h 5s Kd 4c".parse().unwrap();
+        assert_eq!( find_best_low( cards.cards(), EIGHT_OR_BETTER ).unwrap().data.to_string(), "5-4-3-2-A" );
+        let cards: Hand = "As 9d 9c 3h Ts Kd 4c".parse().unwrap();
+        assert!( find_best_low( cards.cards(), EIGHT_OR_BETTER ).is_none() );
+
+        // Omaha lows need two low hole cards and three low board cards
+        let hole: Hand = "As 2s Kd Kc".parse().unwrap();
+        let board: Hand = "3h 4h 5d Qs Jc".parse().unwrap();
+        assert_eq!( find_best_omaha_low( hole.cards(), board.cards(), EIGHT_OR_BETTER ).unwrap().data.to_string(),
+            "5-4-3-2-A" );
+        let hole: Hand = "As Kd Qc Jh".parse().unwrap();
+        assert!( find_best_omaha_low( hole.cards(), board.cards(), EIGHT_OR_BETTER ).is_none() );
+        let board: Hand = "3h 4h Qd Qs Jc".parse().unwrap();
+        let hole: Hand = "As 2s 6d 7c".parse().unwrap();
+        assert!( find_best_omaha_low( hole.cards(), board.cards(), EIGHT_OR_BETTER ).is_none() );
+
+        // high and low are found separately and may share cards
+        let hole: Hand = "As 2s Kd Kc".parse().unwrap();
+        let board: Hand = "3s 4s 5s Qs Jc".parse().unwrap();
+        let best = Variant::OmahaHiLo.best_hi_lo( hole.cards(), board.cards(), false );
+        assert_eq!( best.high.data.rank(), HandRank::StraightFlush );
+        assert_eq!( best.low.unwrap().data.to_string(), "5-4-3-2-A" );
+        assert!( Variant::Omaha.best_low( hole.cards(), board.cards() ).is_none() );
+        assert_eq!( "omaha8".parse::<Variant>(), Ok(Variant::OmahaHiLo) );
+    }
+
+    fn short(s: &str) -> HandWithData {
+        HandWithData::from_hand_with( &s.parse().unwrap(), HandRules::ShortDeck )
+    }
+
+    #[test]
+    fn short_deck_rules() {
+        let d = Deck::new_short();
+        assert_eq!( d.count(), 36 );
+        assert!( d.cards().iter().all(|c| c.rank >= CardRank::Six) );
+        assert_eq!( Variant::ShortDeck.deck().count(), 36 );
+
+        // a flush beats a full house
+        assert!( short("Ah Jh 9h 7h 6h") > short("Ks Kd Kc Qs Qd") );
+        assert!( HandWithData::from_hand(&"Ah Jh 9h 7h 6h".parse().unwrap()) <
+                 HandWithData::from_hand(&"Ks Kd Kc Qs Qd".parse().unwrap()) );
+        assert!( short("Ks Kd Kc Kh Qd") > short("Ah Jh 9h 7h 6h") );
+        assert!( short("Ks Kd Kc Qs Qd") > short("Ac Kd Qh Js 10c") );
+
+        // A-6-7-8-9 is the lowest straight, and a straight flush when suited
+        assert_eq!( short("Ac 6d 7h 8s 9c").rank(), HandRank::Straight );
+        assert!( short("Ac 6d 7h 8s 9c") < short("6c 7d 8h 9s 10c") );
+        assert!( short("Ac 6d 7h 8s 9c") > short("Ac Ad 7h 7s 9c") );
+        assert_eq!( short("As 6s 7s 8s 9s").rank(), HandRank::StraightFlush );
+        assert_eq!( short("Ac 6d 7h 8s 9c").to_string(), " Straight, High: 9\u{2663}" );
+        // but not in the full deck
+        assert_eq!( HandWithData::from_hand(&"Ac 6d 7h 8s 9c".parse().unwrap()).rank(), HandRank::HighCard );
+
+        let cards: Hand = "Ah Jh 9h 7h 6h Ks Kd".parse().unwrap();
+        assert_eq!( find_best_hand_with( cards.cards(), HandRules::ShortDeck, false ).data.rank(), HandRank::Flush );
+        assert_eq!( "short-deck".parse::<Variant>(), Ok(Variant::ShortDeck) );
+    }
+
+    fn wild(s: &str, ranks: &str) -> HandWithData {
+        HandWithData::from_wild_hand( &s.parse().unwrap(), &ranks.parse().unwrap(), HandRules::Standard )
+    }
+
+    #[test]
+    fn jokers() {
+        let joker: Card = "Xh".parse().unwrap();
+        assert_eq!( joker.rank, CardRank::Joker );
+        assert_eq!( joker.name(), "Joker" );
+        assert!( "Xh Xs".parse::<Hand>().is_ok() );
+
+        let mut d = Deck::new();
+        assert!( d.cards().iter().all(|c| c.rank != CardRank::Joker) );
+        d.add_jokers(2);
+        assert_eq!( d.count(), 54 );
+        assert_eq!( d.draw().unwrap(), "Xs".parse().unwrap() );
+        assert_eq!( d.draw().unwrap(), joker );
+
+        assert_eq!( "2".parse::<WildCards>(), Ok(WildCards::rank(CardRank::Two)) );
+        assert_eq!( "2, 10".parse::<WildCards>().unwrap().ranks, vec![CardRank::Two, CardRank::Ten] );
+        assert_eq!( "".parse::<WildCards>(), Ok(WildCards::default()) );
+        assert_eq!( "2 Kh".parse::<WildCards>().err(),
+            Some(ParseCardError::InvalidRank { token: String::from("Kh"), position: 2 }) );
+        assert!( WildCards::default().is_wild(&joker) );
+        assert!( !WildCards::default().is_wild(&"2c".parse().unwrap()) );
+    }
+
+    #[test]
+    fn wild_substitution() {
+        // a joker fills a straight flush rather than making four of a kind
+        let h = wild("9h 10h Jh Qh Xs", "");
+        assert_eq!( h.rank(), HandRank::StraightFlush );
+        assert_eq!( h.to_string(), " Straight Flush, High: K\u{2665} (Joker as K\u{2665})" );
+        // a joker with four of a kind makes five of a kind, the best hand
+        let five = wild("As Ah Ad Ac Xh", "");
+        assert_eq!( five.rank(), HandRank::FiveOfAKind );
+        assert_eq!( five.to_string(), " Five of a Kind, Rank: A\u{2660} (Joker as A\u{2665})" );
+        assert!( five > wild("10s Js Qs Ks As", "") );
+        assert!( five > wild("Ks Kh Kd Kc 2c", "2") );
+
+        // deuces wild: the deuces join the best set, or complete the flush
+        let h = wild("Kc Kd 2h 2s 7c", "2");
+        assert_eq!( h.rank(), HandRank::FourOfAKind );
+        assert_eq!( h.wild().len(), 2 );
+        assert!( h.wild().iter().all(|(_, c)| c.rank == CardRank::King) );
+        assert_eq!( wild("3h 8h Jh 2s Kh", "2").rank(), HandRank::Flush );
+        assert_eq!( wild("3h 4c 5d 2s 7h", "2").to_string(), " Straight, High: 7\u{2665} (2\u{2660} as 6\u{2665})" );
+        assert_eq!( wild("2c 2d 2h 2s Xh", "2").rank(), HandRank::FiveOfAKind );
+        // a flush never repeats a card already in the hand
+        let h = wild("Ah Jh 8h 2c Xs", "2");
+        assert_eq!( h.rank(), HandRank::Flush );
+        let stood_in: Vec<Card> = h.wild().iter().map(|(_, c)| c.clone()).collect();
+        assert_eq!( stood_in, vec!["Qh".parse().unwrap(), "Kh".parse().unwrap()] );
+        // without wild ranks, deuces are natural
+        assert_eq!( wild("Kc Kd 2h 2s 7c", "").rank(), HandRank::TwoPair );
+        assert!( wild("Kc Kd 2h 2s 7c", "").wild().is_empty() );
+
+        let cards: Hand = "Ah Kh Xs 7c 2d 9s 9d".parse().unwrap();
+        let best = find_best_wild_hand( cards.cards(), &WildCards::default(), HandRules::Standard, false );
+        assert_eq!( best.data.rank(), HandRank::ThreeOfAKind );
+        assert!( best.cards.cards().contains(&"Xs".parse().unwrap()) );
+        let hole: Hand = "Xh Kd".parse().unwrap();
+        let board: Hand = "Kc Ks 4h 9c 9d".parse().unwrap();
+        assert_eq!( Variant::Holdem.best_wild_hand( hole.cards(), board.cards(), &WildCards::default(), false ).data.rank(),
+            HandRank::FourOfAKind );
     }
 }
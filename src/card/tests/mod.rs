@@ -1 +1,2 @@
 pub mod card_tests;
+pub mod support;
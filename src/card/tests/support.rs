@@ -0,0 +1,6 @@
+use crate::card::Hand;
+
+/// Parse a hand written out as in the tests, e.g. "Ah Kh".
+pub fn hand(s: &str) -> Hand {
+    s.parse().unwrap()
+}
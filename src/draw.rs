@@ -0,0 +1,216 @@
+use crate::card::{Card, CardRank, Deck, Hand, HandRank, HandWithData, winners};
+use rand::Rng;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrawError {
+    TooFewPlayers,
+    TooManyPlayers,
+    AlreadyDrawn(usize),
+    InvalidDiscard(usize),
+    OutOfCards,
+}
+
+impl fmt::Display for DrawError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DrawError::TooFewPlayers => write!(f, "at least two players are needed"),
+            DrawError::TooManyPlayers => write!(f, "at most eight players can play draw from one deck"),
+            DrawError::AlreadyDrawn(player) => write!(f, "player {} has already drawn", player + 1),
+            DrawError::InvalidDiscard(idx) => write!(f, "there is no card {} to discard", idx + 1),
+            DrawError::OutOfCards => write!(f, "not enough cards left to draw, even after reshuffling"),
+        }
+    }
+}
+
+impl std::error::Error for DrawError {}
+
+/// A five card draw deal: five cards to each player, one draw, showdown.
+pub struct DrawDeal {
+    hands: Vec<Hand>,
+    deck: Deck,
+    // cards thrown away, reshuffled into a new stub when the deck runs out
+    muck: Vec<Card>,
+    drawn: Vec<Option<usize>>,
+}
+
+impl DrawDeal {
+    /// Deal five cards to each player, one at a time, from a shuffled deck.
+    /// With eight players or fewer a full deck always has enough cards left
+    /// for everyone to draw five.
+    pub fn new(players: usize, mut deck: Deck) -> Result<DrawDeal, DrawError> {
+        if players < 2 {
+            return Err(DrawError::TooFewPlayers);
+        }
+        if players > 8 || players * 5 > deck.count() {
+            return Err(DrawError::TooManyPlayers);
+        }
+        let mut hands = vec![Hand::new(); players];
+        for _ in 0..5 {
+            for h in hands.iter_mut() {
+                h.add( deck.draw().unwrap() );
+            }
+        }
+        Ok(DrawDeal { hands, deck, muck: Vec::new(), drawn: vec![None; players] })
+    }
+
+    pub fn hands(&self) -> &[Hand] {
+        &self.hands
+    }
+
+    /// Number of cards the player drew, once they have.
+    pub fn drawn(&self, player: usize) -> Option<usize> {
+        self.drawn[player]
+    }
+
+    /// Cards left in the stub.
+    pub fn stub(&self) -> usize {
+        self.deck.count()
+    }
+
+    /// Throw away the cards at `discards`, positions in the player's hand,
+    /// and draw as many to replace them. If the stub runs out, the cards
+    /// already thrown away by other players are shuffled to make a new one.
+    /// Returns the cards drawn.
+    pub fn draw<R: Rng + ?Sized>(&mut self, player: usize, discards: &[usize],
+                                 rng: &mut R) -> Result<Vec<Card>, DrawError> {
+        if self.drawn[player].is_some() {
+            return Err(DrawError::AlreadyDrawn(player));
+        }
+        let mut positions = discards.to_vec();
+        positions.sort();
+        for (i, p) in positions.iter().enumerate() {
+            if *p >= self.hands[player].count() || (i > 0 && positions[i - 1] == *p) {
+                return Err(DrawError::InvalidDiscard(*p));
+            }
+        }
+        if positions.len() > self.deck.count() + self.muck.len() {
+            return Err(DrawError::OutOfCards);
+        }
+
+        // remove from the back so the other positions stay put
+        let mut thrown = Vec::new();
+        for p in positions.iter().rev() {
+            thrown.push( self.hands[player].remove(*p) );
+        }
+        let mut new_cards = Vec::new();
+        for _ in 0..thrown.len() {
+            if self.deck.count() == 0 {
+                let mut stub = Deck::from_cards( std::mem::take(&mut self.muck) );
+                stub.shuffle_with(rng);
+                self.deck = stub;
+            }
+            let card = self.deck.draw().unwrap();
+            self.hands[player].add( card.clone() );
+            new_cards.push(card);
+        }
+        self.muck.extend(thrown);
+        self.drawn[player] = Some(positions.len());
+        Ok(new_cards)
+    }
+
+    pub fn hand_data(&self, player: usize) -> HandWithData {
+        HandWithData::from_hand( &self.hands[player] )
+    }
+
+    /// Players with the best hand.
+    pub fn winners(&self) -> Vec<usize> {
+        let shown: Vec<HandWithData> = (0..self.hands.len()).map(|i| self.hand_data(i)).collect();
+        winners(&shown)
+    }
+}
+
+// Positions of the cards whose rank appears `n` times in the hand.
+fn positions_of_sets(hand: &Hand, n: usize) -> Vec<usize> {
+    (0..hand.count())
+        .filter(|i| hand.cards().iter().filter(|c| c.rank == hand.card(*i).rank).count() == n)
+        .collect()
+}
+
+// The four cards of one suit, if the hand has them.
+fn four_flush(hand: &Hand) -> Option<Vec<usize>> {
+    (0..hand.count())
+        .map(|i| (0..hand.count()).filter(|j| hand.card(*j).suite == hand.card(i).suite).collect::<Vec<usize>>())
+        .find(|same| same.len() == 4)
+}
+
+// Four cards in a row open at both ends, if the hand has them.
+fn open_ended(hand: &Hand) -> Option<Vec<usize>> {
+    // runs from 2-3-4-5 up to 10-J-Q-K can be filled in at either end
+    (CardRank::Two.index()..=CardRank::Ten.index())
+        .map(|low| {
+            (low..low + 4)
+                .filter_map(|r| hand.cards().iter().position(|c| c.rank.index() == r))
+                .collect::<Vec<usize>>()
+        })
+        .find(|run| run.len() == 4)
+}
+
+/// The cards to throw away under a standard strategy: stand pat with a
+/// straight or better, keep any pair, two pair or trips, draw one to four
+/// to a flush or an open-ended straight, and otherwise keep just an ace.
+pub fn standard_discards(hand: &Hand) -> Vec<usize> {
+    assert!( hand.count() == 5 );
+    let keep = match HandWithData::from_hand(hand).rank() {
+        HandRank::Straight | HandRank::Flush | HandRank::FullHouse |
+        HandRank::FourOfAKind | HandRank::StraightFlush | HandRank::FiveOfAKind => return Vec::new(),
+        HandRank::ThreeOfAKind => positions_of_sets(hand, 3),
+        HandRank::TwoPair | HandRank::Pair => positions_of_sets(hand, 2),
+        HandRank::HighCard => four_flush(hand)
+            .or_else(|| open_ended(hand))
+            .unwrap_or_else(|| hand.cards().iter().position(|c| c.rank == CardRank::Ace).into_iter().collect()),
+    };
+    (0..hand.count()).filter(|i| !keep.contains(i)).collect()
+}
+
+/// How often players stood pat or drew each number of cards.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DrawStats {
+    pub hands: u32,
+    // hands that drew 0, 1, ... 5 cards
+    pub by_count: [u32; 6],
+    // hands that ended up in a better category than they started
+    pub improved: u32,
+}
+
+impl DrawStats {
+    /// Fraction of hands that drew `n` cards; 0 is standing pat.
+    pub fn frequency(&self, n: usize) -> f64 {
+        self.fraction(self.by_count[n])
+    }
+
+    /// Fraction of hands that improved on the draw.
+    pub fn improvement(&self) -> f64 {
+        self.fraction(self.improved)
+    }
+
+    // Zero when no hands were dealt.
+    fn fraction(&self, count: u32) -> f64 {
+        if self.hands == 0 { 0.0 } else { count as f64 / self.hands as f64 }
+    }
+}
+
+/// Deal `deals` hands to `players` players, all following the standard
+/// strategy, and count how many cards each drew.
+pub fn simulate<R: Rng + ?Sized>(players: usize, deals: u32, rng: &mut R) -> Result<DrawStats, DrawError> {
+    let mut stats = DrawStats::default();
+    for _ in 0..deals {
+        let mut d = Deck::new();
+        d.shuffle_with(rng);
+        let mut deal = DrawDeal::new(players, d)?;
+        for p in 0..players {
+            let before = deal.hand_data(p).rank();
+            let discards = standard_discards( &deal.hands()[p] );
+            deal.draw(p, &discards, rng)?;
+            stats.hands += 1;
+            stats.by_count[discards.len()] += 1;
+            if deal.hand_data(p).rank() > before {
+                stats.improved += 1;
+            }
+        }
+    }
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests;
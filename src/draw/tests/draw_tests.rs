@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::draw::*;
+    use crate::card::tests::support::hand;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    // Two players, dealt alternately: player 1 gets `p0`, player 2 `p1`,
+    // and `stub` is left to draw from.
+    fn two_hands(p0: &str, p1: &str, stub: &str) -> DrawDeal {
+        let a: Vec<&str> = p0.split_whitespace().collect();
+        let b: Vec<&str> = p1.split_whitespace().collect();
+        let mut cards: Vec<&str> = Vec::new();
+        for i in 0..5 {
+            cards.push(a[i]);
+            cards.push(b[i]);
+        }
+        let deck = format!("{} {}", cards.join(" "), stub);
+        DrawDeal::new( 2, deck.parse().unwrap() ).unwrap()
+    }
+
+    #[test]
+    fn deal() {
+        let d = DrawDeal::new( 6, Deck::new_seeded(1) ).unwrap();
+        assert_eq!( d.hands().len(), 6 );
+        assert!( d.hands().iter().all(|h| h.count() == 5) );
+        assert_eq!( d.stub(), 52 - 30 );
+        assert_eq!( d.drawn(0), None );
+
+        assert_eq!( DrawDeal::new( 1, Deck::new() ).err(), Some(DrawError::TooFewPlayers) );
+        assert_eq!( DrawDeal::new( 9, Deck::new() ).err(), Some(DrawError::TooManyPlayers) );
+    }
+
+    #[test]
+    fn draw_replaces_discards() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut d = two_hands( "Ks Kd 7c 4h 2s", "Ah Qh 9h 5h 3c", "Kh 8d 6s 2d 3s" );
+        assert_eq!( d.hands()[0], hand("Ks Kd 7c 4h 2s") );
+
+        let drawn = d.draw( 0, &[4, 2, 3], &mut rng ).unwrap();
+        assert_eq!( drawn, hand("Kh 8d 6s").cards().to_vec() );
+        assert_eq!( d.hands()[0], hand("Ks Kd Kh 8d 6s") );
+        assert_eq!( d.drawn(0), Some(3) );
+        assert_eq!( d.draw( 0, &[], &mut rng ).err(), Some(DrawError::AlreadyDrawn(0)) );
+
+        assert_eq!( d.draw( 1, &[5], &mut rng ).err(), Some(DrawError::InvalidDiscard(5)) );
+        assert_eq!( d.draw( 1, &[4, 4], &mut rng ).err(), Some(DrawError::InvalidDiscard(4)) );
+        assert_eq!( d.drawn(1), None );
+        assert_eq!( d.draw( 1, &[], &mut rng ).unwrap(), Vec::new() );
+        assert_eq!( d.drawn(1), Some(0) );
+
+        assert_eq!( d.hand_data(0).rank(), HandRank::ThreeOfAKind );
+        assert_eq!( d.winners(), vec![0] );
+    }
+
+    #[test]
+    fn reshuffles_the_muck() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut d = two_hands( "Ks Kd 7c 4h 2s", "Ah Qh 9h 5h 3c", "Kh 8d" );
+        // player 1 takes the last two cards of the stub, so player 2 draws
+        // from player 1's discards reshuffled
+        assert_eq!( d.draw( 0, &[0, 1], &mut rng ).unwrap().len(), 2 );
+        assert_eq!( d.stub(), 0 );
+        let drawn = d.draw( 1, &[0, 4], &mut rng ).unwrap();
+        assert_eq!( drawn.len(), 2 );
+        assert!( drawn.iter().all(|c| hand("Ks Kd").cards().contains(c)) );
+        assert_eq!( d.stub(), 0 );
+
+        let mut d = two_hands( "Ks Kd 7c 4h 2s", "Ah Qh 9h 5h 3c", "Kh" );
+        assert_eq!( d.draw( 0, &[0, 1], &mut rng ).err(), Some(DrawError::OutOfCards) );
+    }
+
+    #[test]
+    fn standard_strategy() {
+        let discards = |s: &str| standard_discards( &hand(s) );
+        assert_eq!( discards("5c 6d 7h 8s 9c"), Vec::<usize>::new() );
+        assert_eq!( discards("2h 9h Jh 4h Kh"), Vec::<usize>::new() );
+        // keep trips, two pair or a pair
+        assert_eq!( discards("Qc 4d Qh Qs 9c"), vec![1, 4] );
+        assert_eq!( discards("Qc 4d Qh 4s 9c"), vec![4] );
+        assert_eq!( discards("2c Ad 9h 2s 7c"), vec![1, 2, 4] );
+        // draw one to a flush or an open-ended straight
+        assert_eq!( discards("2h 9h Jc 4h Kh"), vec![2] );
+        assert_eq!( discards("5c 6d As 7h 8s"), vec![2] );
+        // a gutshot is not worth drawing to, but an ace is worth keeping
+        assert_eq!( discards("5c 6d As 8h 9s"), vec![0, 1, 3, 4] );
+        assert_eq!( discards("Jc Qd Ks Ah 3s"), vec![0, 1, 2, 4] );
+        assert_eq!( discards("2c 9d Js 4h Kd"), vec![0, 1, 2, 3, 4] );
+    }
+
+    #[test]
+    fn simulation() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let stats = simulate( 8, 500, &mut rng ).unwrap();
+        assert_eq!( stats.hands, 4000 );
+        assert_eq!( stats.by_count.iter().sum::<u32>(), 4000 );
+        let total: f64 = (0..=5).map(|n| stats.frequency(n)).sum();
+        assert!( (total - 1.0).abs() < 1e-9 );
+        // about 42% of hands hold a pair and draw three
+        assert!( (stats.frequency(3) - 0.42).abs() < 0.03 );
+        assert!( stats.improved > 0 && stats.improved < stats.hands );
+
+        let again = simulate( 8, 500, &mut StdRng::seed_from_u64(3) ).unwrap();
+        assert_eq!( stats, again );
+
+        let none = DrawStats::default();
+        assert_eq!( (none.frequency(0), none.improvement()), (0.0, 0.0) );
+    }
+}
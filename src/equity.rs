@@ -0,0 +1,488 @@
+use crate::card::{Card, Deck, Hand, HandWithData, LowHand, Variant, binomial, combinations, winners};
+use crate::eval::{CardSet, evaluate};
+use crate::range::Range;
+use rand::Rng;
+use std::fmt;
+
+/// Error returned when an equity calculation is given an impossible deal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquityError {
+    TooFewPlayers,
+    WrongHoleCards { player: usize, count: usize, expected: usize },
+    BoardTooLarge(usize),
+    WrongBoard { count: usize, min: usize, max: usize },
+    DuplicateCard(Card),
+    NotInDeck(Card),
+    NotEnoughCards,
+    EmptyRange(usize),
+    NoCompatibleHands,
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EquityError::TooFewPlayers =>
+                write!(f, "at least two players are needed"),
+            EquityError::WrongHoleCards { player, count, expected } =>
+                write!(f, "player {} has {} hole cards, expected {}", player + 1, count, expected),
+            EquityError::BoardTooLarge(count) =>
+                write!(f, "board has {} cards, at most 5 allowed", count),
+            EquityError::WrongBoard { count, min, max } =>
+                write!(f, "board has {} cards, expected {} to {}", count, min, max),
+            EquityError::DuplicateCard(card) =>
+                write!(f, "card {} is dealt more than once", card),
+            EquityError::NotInDeck(card) =>
+                write!(f, "card {} is not used in this game", card),
+            EquityError::NotEnoughCards =>
+                write!(f, "not enough cards left in the deck to complete the board"),
+            EquityError::EmptyRange(player) =>
+                write!(f, "player {} has no hands left in their range once blocked cards are removed", player + 1),
+            EquityError::NoCompatibleHands =>
+                write!(f, "no hands from the ranges can be dealt together"),
+        }
+    }
+}
+
+impl std::error::Error for EquityError {}
+
+/// Accumulated outcomes for one player.
+#[derive(Debug, Clone, Default)]
+pub struct PlayerEquity {
+    pub wins: f64,
+    pub ties: f64,
+    // sum of the fraction of the pot won, and of its square
+    share: f64,
+    share_sq: f64,
+}
+
+impl PlayerEquity {
+    // Add a board on which the player won a share of the pot, split `ways` ways.
+    fn add(&mut self, ways: usize, weight: f64) {
+        let share = 1.0 / ways as f64;
+        if ways == 1 {
+            self.wins += weight;
+        } else {
+            self.ties += weight;
+        }
+        self.share += weight * share;
+        self.share_sq += weight * share * share;
+    }
+}
+
+/// Outcome of an equity calculation. Percentages are fractions of the
+/// boards dealt; a tie counts towards equity as an equal share of the pot.
+/// In split games winning either half of the pot counts as half a win.
+#[derive(Debug, Clone)]
+pub struct EquityResult {
+    pub players: Vec<PlayerEquity>,
+    /// The boards dealt, each counted by its weight.
+    pub boards: f64,
+    /// How many boards were dealt, whatever their weights.
+    pub deals: u64,
+    pub exact: bool,
+}
+
+impl EquityResult {
+    fn new(players: usize, exact: bool) -> EquityResult {
+        EquityResult { players: vec![PlayerEquity::default(); players], boards: 0.0, deals: 0, exact }
+    }
+
+    // Record one board, won by the players listed, counted `weight` times.
+    fn record(&mut self, winners: &[usize], weight: f64) {
+        for w in winners {
+            self.players[*w].add(winners.len(), weight);
+        }
+        self.boards += weight;
+        self.deals += 1;
+    }
+
+    // Record one board in a split game, with half the pot to the `high`
+    // winners and half to the `low`. A player's two halves count as one
+    // share of that board.
+    fn record_hi_lo(&mut self, high: &[usize], low: &[usize]) {
+        let mut shares = vec![0.0; self.players.len()];
+        for winners in [high, low] {
+            for w in winners {
+                let p = &mut self.players[*w];
+                if winners.len() == 1 {
+                    p.wins += 0.5;
+                } else {
+                    p.ties += 0.5;
+                }
+                shares[*w] += 0.5 / winners.len() as f64;
+            }
+        }
+        for (p, share) in self.players.iter_mut().zip(shares) {
+            p.share += share;
+            p.share_sq += share * share;
+        }
+        self.boards += 1.0;
+        self.deals += 1;
+    }
+
+    pub fn win(&self, player: usize) -> f64 {
+        self.players[player].wins / self.boards
+    }
+
+    pub fn tie(&self, player: usize) -> f64 {
+        self.players[player].ties / self.boards
+    }
+
+    pub fn loss(&self, player: usize) -> f64 {
+        1.0 - self.win(player) - self.tie(player)
+    }
+
+    pub fn equity(&self, player: usize) -> f64 {
+        self.players[player].share / self.boards
+    }
+
+    /// Standard error of the equity estimate; zero for exact results.
+    pub fn std_error(&self, player: usize) -> f64 {
+        if self.exact || self.boards < 2.0 {
+            return 0.0;
+        }
+        let mean = self.equity(player);
+        let var = self.players[player].share_sq / self.boards - mean * mean;
+        (var.max(0.0) / (self.boards - 1.0)).sqrt()
+    }
+}
+
+// Check the deal is possible and return the deck of cards still unseen.
+fn remaining_deck(game: Variant, hands: &[Hand], board: &Hand, dead: &Hand) -> Result<Deck, EquityError> {
+    if hands.len() < 2 {
+        return Err(EquityError::TooFewPlayers);
+    }
+    let expected = game.hole_cards();
+    for (i, h) in hands.iter().enumerate() {
+        if h.count() != expected {
+            return Err(EquityError::WrongHoleCards { player: i, count: h.count(), expected });
+        }
+    }
+    if board.count() > 5 {
+        return Err(EquityError::BoardTooLarge(board.count()));
+    }
+    let deck = unseen_deck( game.deck(), hands.iter().chain([board, dead]) )?;
+    if deck.count() + board.count() < 5 {
+        return Err(EquityError::NotEnoughCards);
+    }
+    Ok(deck)
+}
+
+// The deck without the given cards, which must all be different and in it.
+pub(crate) fn unseen_deck<'a>(mut deck: Deck, known: impl Iterator<Item = &'a Hand>) -> Result<Deck, EquityError> {
+    let full = deck.clone();
+    for h in known {
+        for c in h.cards() {
+            if !deck.remove(c) {
+                if !full.cards().contains(c) {
+                    return Err(EquityError::NotInDeck(c.clone()));
+                }
+                return Err(EquityError::DuplicateCard(c.clone()));
+            }
+        }
+    }
+    Ok(deck)
+}
+
+// Record who won on a complete board. In split games with a qualifying
+// low, each half of the pot counts as half a board.
+fn record_board(res: &mut EquityResult, game: Variant, hands: &[Hand], board: &[Card]) {
+    let highs: Vec<HandWithData> = hands.iter()
+        .map(|h| game.best_hand( h.cards(), board, false ).data)
+        .collect();
+    if game.hi_lo() {
+        let lows: Vec<Option<LowHand>> = hands.iter()
+            .map(|h| game.best_low( h.cards(), board ).map(|b| b.data))
+            .collect();
+        if lows.iter().any(|l| l.is_some()) {
+            res.record_hi_lo( &winners(&highs), &winners(&lows) );
+            return;
+        }
+    }
+    res.record( &winners(&highs), 1.0 );
+}
+
+/// Estimate each player's equity by dealing `trials` random completions
+/// of the board from the cards not held by a player, on the board or dead.
+pub fn simulate<R: Rng + ?Sized>(game: Variant, hands: &[Hand], board: &Hand, dead: &Hand,
+                                 trials: u32, rng: &mut R) -> Result<EquityResult, EquityError> {
+    let deck = remaining_deck(game, hands, board, dead)?;
+    let mut res = EquityResult::new(hands.len(), false);
+    for _ in 0..trials {
+        let mut d = deck.clone();
+        d.shuffle_with(rng);
+        let mut full = board.clone();
+        while full.count() < 5 {
+            full.add( d.draw().unwrap() );
+        }
+        record_board( &mut res, game, hands, full.cards() );
+    }
+    Ok(res)
+}
+
+/// Number of distinct ways the board can be completed for this deal.
+pub fn board_count(game: Variant, hands: &[Hand], board: &Hand, dead: &Hand) -> Result<u64, EquityError> {
+    let deck = remaining_deck(game, hands, board, dead)?;
+    Ok(binomial(deck.count() as u64, 5 - board.count() as u64))
+}
+
+/// Work out each player's exact equity by dealing every possible
+/// completion of the board from the cards still unseen.
+pub fn enumerate(game: Variant, hands: &[Hand], board: &Hand, dead: &Hand) -> Result<EquityResult, EquityError> {
+    let deck = remaining_deck(game, hands, board, dead)?;
+    let mut res = EquityResult::new(hands.len(), true);
+    let rest = deck.cards();
+    for c in combinations(rest.len(), 5 - board.count()) {
+        let mut full = board.clone();
+        for i in c {
+            full.add( rest[i].clone() );
+        }
+        record_board( &mut res, game, hands, full.cards() );
+    }
+    Ok(res)
+}
+
+/// Enumerate exactly when there are no more boards than `trials`,
+/// otherwise estimate by dealing `trials` random boards.
+pub fn calculate<R: Rng + ?Sized>(game: Variant, hands: &[Hand], board: &Hand, dead: &Hand,
+                                  trials: u32, rng: &mut R) -> Result<EquityResult, EquityError> {
+    if board_count(game, hands, board, dead)? <= trials as u64 {
+        enumerate(game, hands, board, dead)
+    } else {
+        simulate(game, hands, board, dead, trials, rng)
+    }
+}
+
+/// Estimate the equity of `hole` against `opponents` players holding random
+/// hands, dealing both their cards and the rest of the board at random.
+/// Player 0 in the result is `hole`, the others are the opponents.
+pub fn simulate_vs_random<R: Rng + ?Sized>(hole: &Hand, board: &Hand, opponents: usize,
+                                           trials: u32, rng: &mut R) -> Result<EquityResult, EquityError> {
+    if opponents < 1 {
+        return Err(EquityError::TooFewPlayers);
+    }
+    if hole.count() != 2 {
+        return Err(EquityError::WrongHoleCards { player: 0, count: hole.count(), expected: 2 });
+    }
+    if board.count() > 5 {
+        return Err(EquityError::BoardTooLarge(board.count()));
+    }
+    let deck = unseen_deck( Deck::new(), [hole, board].into_iter() )?;
+    if deck.count() < 2 * opponents + 5 - board.count() {
+        return Err(EquityError::NotEnoughCards);
+    }
+    let mut res = EquityResult::new(opponents + 1, false);
+    let mut values = vec![0; opponents + 1];
+    for _ in 0..trials {
+        let mut d = deck.clone();
+        d.shuffle_with(rng);
+        let mut common = CardSet::from_hand(board);
+        for _ in board.count()..5 {
+            common.insert( &d.draw().unwrap() );
+        }
+        values[0] = evaluate( common.union(CardSet::from_hand(hole)) );
+        for v in values.iter_mut().skip(1) {
+            let mut cards = common;
+            cards.insert( &d.draw().unwrap() );
+            cards.insert( &d.draw().unwrap() );
+            *v = evaluate(cards);
+        }
+        res.record( &winners(&values), 1.0 );
+    }
+    Ok(res)
+}
+
+/// How one combo in a player's range fared in a range-versus-range
+/// calculation, over the boards it was dealt on.
+#[derive(Debug, Clone)]
+pub struct ComboEquity {
+    pub hand: Hand,
+    /// The combo's weight in its range.
+    pub weight: f64,
+    pub result: PlayerEquity,
+    /// The boards the combo was dealt on, each counted by its weight.
+    pub boards: f64,
+    /// How many boards the combo was dealt on.
+    pub deals: u64,
+}
+
+impl ComboEquity {
+    pub fn win(&self) -> f64 {
+        self.result.wins / self.boards
+    }
+
+    pub fn tie(&self) -> f64 {
+        self.result.ties / self.boards
+    }
+
+    pub fn equity(&self) -> f64 {
+        self.result.share / self.boards
+    }
+}
+
+/// Outcome of a range-versus-range calculation: each range's overall
+/// equity, and the equity of every combo in each range.
+#[derive(Debug, Clone)]
+pub struct RangeEquityResult {
+    pub overall: EquityResult,
+    pub combos: Vec<Vec<ComboEquity>>,
+}
+
+impl RangeEquityResult {
+    // Record one deal of the chosen combos, with each player's strength.
+    fn record(&mut self, chosen: &[usize], values: &[u16], weight: f64) {
+        let w = winners(values);
+        self.overall.record(&w, weight);
+        for (player, combo) in chosen.iter().enumerate() {
+            let c = &mut self.combos[player][*combo];
+            if w.contains(&player) {
+                c.result.add(w.len(), weight);
+            }
+            c.boards += weight;
+            c.deals += 1;
+        }
+    }
+}
+
+/// Picks indices at random, each in proportion to its weight.
+pub(crate) struct WeightedPick {
+    // running totals of the weights
+    totals: Vec<f64>,
+}
+
+impl WeightedPick {
+    pub(crate) fn new(weights: impl Iterator<Item = f64>) -> WeightedPick {
+        WeightedPick { totals: weights.scan(0.0, |acc, w| { *acc += w; Some(*acc) }).collect() }
+    }
+
+    pub(crate) fn pick<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let x = rng.gen::<f64>() * self.totals[self.totals.len() - 1];
+        self.totals.partition_point(|acc| *acc <= x).min(self.totals.len() - 1)
+    }
+}
+
+// Each player's combos as card sets, with their weights.
+type Combos = Vec<Vec<(CardSet, f64)>>;
+
+// Whether some choice of one combo per player, from `player` on, shares no
+// cards with `used` or with each other.
+fn compatible(combos: &Combos, player: usize, used: CardSet) -> bool {
+    player == combos.len() ||
+        combos[player].iter().any(|(c, _)| !used.intersects(*c) && compatible(combos, player + 1, used.union(*c)))
+}
+
+// Deal every choice of combos that can go together with every completion
+// of the board, counting each by the product of the combos' weights.
+fn enumerate_ranges(res: &mut RangeEquityResult, combos: &Combos, chosen: &mut Vec<usize>,
+                    used: CardSet, board: CardSet, deck: &[Card]) {
+    let player = chosen.len();
+    if player == combos.len() {
+        let weight: f64 = chosen.iter().enumerate().map(|(p, i)| combos[p][*i].1).product();
+        let rest: Vec<&Card> = deck.iter().filter(|c| !used.contains(c)).collect();
+        let mut values = vec![0; combos.len()];
+        for c in combinations(rest.len(), 5 - board.count()) {
+            let mut common = board;
+            for i in c {
+                common.insert( rest[i] );
+            }
+            for (p, v) in values.iter_mut().enumerate() {
+                *v = evaluate( common.union(combos[p][chosen[p]].0) );
+            }
+            res.record(chosen, &values, weight);
+        }
+        return;
+    }
+    for (i, (c, _)) in combos[player].iter().enumerate() {
+        if !used.intersects(*c) {
+            chosen.push(i);
+            enumerate_ranges(res, combos, chosen, used.union(*c), board, deck);
+            chosen.pop();
+        }
+    }
+}
+
+/// Work out the equity of each hold'em range against the others on the
+/// given board. Combos blocked by the board or dead cards are dropped, and
+/// combos that share cards are never dealt together; each combo counts as
+/// often as its weight. Every deal is enumerated when there are no more
+/// than `trials`, otherwise `trials` deals are sampled, each combo chosen
+/// in proportion to its weight.
+pub fn range_equity<R: Rng + ?Sized>(ranges: &[Range], board: &Hand, dead: &Hand,
+                                     trials: u32, rng: &mut R) -> Result<RangeEquityResult, EquityError> {
+    if ranges.len() < 2 {
+        return Err(EquityError::TooFewPlayers);
+    }
+    if board.count() > 5 {
+        return Err(EquityError::BoardTooLarge(board.count()));
+    }
+    let deck = unseen_deck( Deck::new(), [board, dead].into_iter() )?;
+    if deck.count() < 2 * ranges.len() + 5 - board.count() {
+        return Err(EquityError::NotEnoughCards);
+    }
+
+    let mut known = board.cards().to_vec();
+    known.extend_from_slice( dead.cards() );
+    let mut combos: Combos = Vec::new();
+    let mut res = RangeEquityResult { overall: EquityResult::new(ranges.len(), false), combos: Vec::new() };
+    for (i, r) in ranges.iter().enumerate() {
+        let mut r = r.clone();
+        r.remove_blocked(&known);
+        if r.count() == 0 {
+            return Err(EquityError::EmptyRange(i));
+        }
+        combos.push( r.combos().iter().map(|c| (CardSet::from_hand(&c.hand), c.weight)).collect() );
+        res.combos.push( r.combos().iter()
+            .map(|c| ComboEquity { hand: c.hand.clone(), weight: c.weight, result: PlayerEquity::default(),
+                                   boards: 0.0, deals: 0 })
+            .collect() );
+    }
+    let board_set = CardSet::from_hand(board);
+    if !compatible(&combos, 0, board_set) {
+        return Err(EquityError::NoCompatibleHands);
+    }
+
+    // count the deals as if no two combos clashed, which can only overstate it
+    let boards = binomial((deck.count() - 2 * ranges.len()) as u64, 5 - board.count() as u64);
+    let deals = combos.iter().fold(boards, |acc, c| acc.saturating_mul(c.len() as u64));
+    if deals <= trials as u64 {
+        res.overall.exact = true;
+        enumerate_ranges(&mut res, &combos, &mut Vec::new(), board_set, board_set, deck.cards());
+        return Ok(res);
+    }
+
+    let picks: Vec<WeightedPick> = combos.iter().map(|c| WeightedPick::new(c.iter().map(|(_, w)| *w))).collect();
+    let cards = deck.cards();
+    let mut chosen = vec![0; ranges.len()];
+    let mut values = vec![0; ranges.len()];
+    for _ in 0..trials {
+        // deal each player a combo, dealing again whenever two clash
+        let mut used = board_set;
+        while used == board_set {
+            for (p, pick) in picks.iter().enumerate() {
+                chosen[p] = pick.pick(rng);
+                let c = combos[p][chosen[p]].0;
+                if used.intersects(c) {
+                    used = board_set;
+                    break;
+                }
+                used = used.union(c);
+            }
+        }
+        let mut common = board_set;
+        while common.count() < 5 {
+            let c = &cards[rng.gen_range(0..cards.len())];
+            if !used.contains(c) {
+                used.insert(c);
+                common.insert(c);
+            }
+        }
+        for (p, v) in values.iter_mut().enumerate() {
+            *v = evaluate( common.union(combos[p][chosen[p]].0) );
+        }
+        res.record(&chosen, &values, 1.0);
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests;
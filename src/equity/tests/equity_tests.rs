@@ -0,0 +1,293 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::equity::*;
+    use crate::range::Range;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn hands(s: &[&str]) -> Vec<Hand> {
+        s.iter().map(|h| h.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn aces_vs_kings() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let res = simulate( Variant::Holdem, &hands(&["As Ah", "Ks Kh"]), &Hand::new(), &Hand::new(), 3000, &mut rng ).unwrap();
+        assert!( !res.exact );
+        assert_eq!( res.boards, 3000.0 );
+        // exact equity is 82.4%
+        assert!( (res.equity(0) - 0.824).abs() < 4.0 * res.std_error(0) );
+        assert!( res.std_error(0) > 0.0 && res.std_error(0) < 0.01 );
+        assert!( (res.equity(0) + res.equity(1) - 1.0).abs() < 1e-9 );
+        assert!( (res.win(0) + res.tie(0) + res.loss(0) - 1.0).abs() < 1e-9 );
+        assert!( (res.win(0) - res.loss(1)).abs() < 1e-9 );
+    }
+
+    #[test]
+    fn complete_board() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let board: Hand = "2c 7d 9h Js Qc".parse().unwrap();
+        let res = simulate( Variant::Holdem, &hands(&["As Ah", "Ks Kh", "9c 9s"]), &board, &Hand::new(), 50, &mut rng ).unwrap();
+        assert_eq!( res.win(2), 1.0 );
+        assert_eq!( res.loss(0), 1.0 );
+        assert_eq!( res.equity(1), 0.0 );
+        assert_eq!( res.std_error(2), 0.0 );
+    }
+
+    #[test]
+    fn split_pots() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let board: Hand = "2c 3d 4h 5s".parse().unwrap();
+        let dead: Hand = "6c 6d 6h 6s".parse().unwrap();
+        // both players hold a wheel that can no longer be improved by a six
+        let res = simulate( Variant::Holdem, &hands(&["Ac Kd", "Ad Kc"]), &board, &dead, 200, &mut rng ).unwrap();
+        assert_eq!( res.tie(0), 1.0 );
+        assert_eq!( res.tie(1), 1.0 );
+        assert!( (res.equity(0) - 0.5).abs() < 1e-9 );
+    }
+
+    #[test]
+    fn reproducible() {
+        let h = hands(&["As Kd", "7c 7h"]);
+        let board: Hand = "Qs 7s 2d".parse().unwrap();
+        let r1 = simulate( Variant::Holdem, &h, &board, &Hand::new(), 300, &mut StdRng::seed_from_u64(9) ).unwrap();
+        let r2 = simulate( Variant::Holdem, &h, &board, &Hand::new(), 300, &mut StdRng::seed_from_u64(9) ).unwrap();
+        assert_eq!( r1.equity(0), r2.equity(0) );
+        assert_eq!( r1.tie(1), r2.tie(1) );
+    }
+
+    #[test]
+    fn invalid_deals() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let none = Hand::new();
+        assert_eq!( simulate( Variant::Holdem, &hands(&["As Kd"]), &none, &none, 1, &mut rng ).err(),
+            Some(EquityError::TooFewPlayers) );
+        assert_eq!( simulate( Variant::Holdem, &hands(&["As Kd", "Qc"]), &none, &none, 1, &mut rng ).err(),
+            Some(EquityError::WrongHoleCards { player: 1, count: 1, expected: 2 }) );
+        assert_eq!( simulate( Variant::Holdem, &hands(&["As Kd", "Qc Qd"]), &"2c 3c 4c 5c 6c 7c".parse().unwrap(), &none, 1, &mut rng ).err(),
+            Some(EquityError::BoardTooLarge(6)) );
+        assert_eq!( simulate( Variant::Holdem, &hands(&["As Kd", "Qc Qd"]), &"2c Kd 4c".parse().unwrap(), &none, 1, &mut rng ).err(),
+            Some(EquityError::DuplicateCard("Kd".parse().unwrap())) );
+        assert_eq!( simulate( Variant::Holdem, &hands(&["As Kd", "Qc Qd"]), &none, &"Qc".parse().unwrap(), 1, &mut rng ).err(),
+            Some(EquityError::DuplicateCard("Qc".parse().unwrap())) );
+    }
+
+    #[test]
+    fn exact_river_and_turn() {
+        let h = hands(&["As Kd", "7c 7h"]);
+        let board: Hand = "Qs 7s 2d Ks".parse().unwrap();
+        assert_eq!( board_count( Variant::Holdem, &h, &board, &Hand::new() ).unwrap(), 44 );
+        let res = enumerate( Variant::Holdem, &h, &board, &Hand::new() ).unwrap();
+        assert!( res.exact );
+        assert_eq!( res.boards, 44.0 );
+        // only a spade that does not pair the board beats the set of sevens
+        assert_eq!( res.players[0].wins, 8.0 );
+        assert_eq!( res.players[1].wins, 36.0 );
+        assert_eq!( res.std_error(0), 0.0 );
+
+        let full: Hand = "Qs 7s 2d Ks 3c".parse().unwrap();
+        let res = enumerate( Variant::Holdem, &h, &full, &Hand::new() ).unwrap();
+        assert_eq!( res.boards, 1.0 );
+        assert_eq!( res.win(1), 1.0 );
+    }
+
+    #[test]
+    fn exact_split_ties() {
+        // three way: two players chop whenever neither pairs
+        let h = hands(&["Ac Kd", "Ad Kc", "2s 3s"]);
+        let board: Hand = "Qh Jh 9d 4c".parse().unwrap();
+        let res = enumerate( Variant::Holdem, &h, &board, &Hand::new() ).unwrap();
+        assert_eq!( res.boards, 42.0 );
+        let total: f64 = (0..3).map(|i| res.equity(i)).sum();
+        assert!( (total - 1.0).abs() < 1e-9 );
+        assert_eq!( res.equity(0), res.equity(1) );
+        assert!( res.tie(0) > 0.0 );
+    }
+
+    #[test]
+    fn exact_matches_simulation() {
+        let h = hands(&["As Kd", "Jc 10c"]);
+        let board: Hand = "Qc 7c 2d".parse().unwrap();
+        let exact = enumerate( Variant::Holdem, &h, &board, &Hand::new() ).unwrap();
+        assert_eq!( exact.boards, 990.0 );
+        let mut rng = StdRng::seed_from_u64(12);
+        let sampled = simulate( Variant::Holdem, &h, &board, &Hand::new(), 2000, &mut rng ).unwrap();
+        assert!( (sampled.equity(0) - exact.equity(0)).abs() < 4.0 * sampled.std_error(0) );
+    }
+
+    #[test]
+    fn calculate_switches_mode() {
+        let mut rng = StdRng::seed_from_u64(13);
+        let h = hands(&["As Kd", "Jc 10c"]);
+        let flop: Hand = "Qc 7c 2d".parse().unwrap();
+        assert!( calculate( Variant::Holdem, &h, &flop, &Hand::new(), 1000, &mut rng ).unwrap().exact );
+        assert!( !calculate( Variant::Holdem, &h, &flop, &Hand::new(), 500, &mut rng ).unwrap().exact );
+        assert_eq!( board_count( Variant::Holdem, &h, &Hand::new(), &Hand::new() ).unwrap(), 1712304 );
+        assert!( !calculate( Variant::Holdem, &h, &Hand::new(), &Hand::new(), 100, &mut rng ).unwrap().exact );
+    }
+
+    #[test]
+    fn versus_random_hands() {
+        let mut rng = StdRng::seed_from_u64(14);
+        let aces: Hand = "As Ah".parse().unwrap();
+        let res = simulate_vs_random( &aces, &Hand::new(), 1, 4000, &mut rng ).unwrap();
+        // aces win about 85% against one random hand
+        assert!( (res.equity(0) - 0.852).abs() < 4.0 * res.std_error(0) );
+        let res = simulate_vs_random( &aces, &Hand::new(), 5, 2000, &mut rng ).unwrap();
+        assert_eq!( res.players.len(), 6 );
+        assert!( res.equity(0) > 0.4 && res.equity(0) < 0.6 );
+
+        // the nuts on the river cannot lose
+        let board: Hand = "Ks Qs Js 10s 2d".parse().unwrap();
+        let res = simulate_vs_random( &aces, &board, 3, 200, &mut rng ).unwrap();
+        assert_eq!( res.win(0), 1.0 );
+
+        assert_eq!( simulate_vs_random( &aces, &Hand::new(), 0, 1, &mut rng ).err(),
+            Some(EquityError::TooFewPlayers) );
+        assert_eq!( simulate_vs_random( &aces, &"Ah 2c 3d".parse().unwrap(), 1, 1, &mut rng ).err(),
+            Some(EquityError::DuplicateCard("Ah".parse().unwrap())) );
+
+        // 50 unseen cards cover 22 opponents and a board, but not 23
+        assert!( simulate_vs_random( &aces, &Hand::new(), 22, 10, &mut rng ).is_ok() );
+        assert_eq!( simulate_vs_random( &aces, &Hand::new(), 23, 10, &mut rng ).err(),
+            Some(EquityError::NotEnoughCards) );
+        assert_eq!( simulate_vs_random( &aces, &board, 23, 10, &mut rng ).err(),
+            Some(EquityError::NotEnoughCards) );
+    }
+
+    #[test]
+    fn omaha_equity() {
+        let mut rng = StdRng::seed_from_u64(15);
+        let h = hands(&["As Ah Kd Qd", "Jc Tc 9s 8s"]);
+        let board: Hand = "2c 7h Td".parse().unwrap();
+        let res = calculate( Variant::Omaha, &h, &board, &Hand::new(), 1000, &mut rng ).unwrap();
+        assert!( res.exact );
+        assert_eq!( res.boards, 820.0 );
+        assert!( res.equity(1) > res.equity(0) );
+
+        // in hold'em the same four cards would be a wrong deal
+        assert_eq!( calculate( Variant::Holdem, &h, &board, &Hand::new(), 1000, &mut rng ).err(),
+            Some(EquityError::WrongHoleCards { player: 0, count: 4, expected: 2 }) );
+
+        let h = hands(&["As Ah Kd Qd Jc Tc", "9s 8s 7s 6s 5s 4s", "2c 2d 3c 3d 4c 4d", "5c 5d 6c 6d 7c 7d",
+                        "8c 8d 9c 9d Ts Th", "Js Jh Qs Qh Ks Kh", "Ac Ad Kc Qc Jd Td", "8h 9h 2h 3h 4h 5h"]);
+        assert_eq!( calculate( Variant::Omaha6, &h, &Hand::new(), &Hand::new(), 1, &mut rng ).err(),
+            Some(EquityError::NotEnoughCards) );
+    }
+
+    #[test]
+    fn hi_lo_equity() {
+        let mut rng = StdRng::seed_from_u64(16);
+        // same high hand, but only one player can make a low
+        let h = hands(&["Ks Kd 2c 3c", "Kh Kc Qd Qh"]);
+        let board: Hand = "4d 5h 8s Jc".parse().unwrap();
+        let res = calculate( Variant::OmahaHiLo, &h, &board, &Hand::new(), 1000, &mut rng ).unwrap();
+        assert!( res.exact );
+        assert_eq!( res.boards, 40.0 );
+        assert!( res.equity(0) > 0.5 );
+        assert!( (res.equity(0) + res.equity(1) - 1.0).abs() < 1e-9 );
+
+        // a board with no low possible plays like high only
+        let board: Hand = "9d Th Js".parse().unwrap();
+        let hilo = enumerate( Variant::OmahaHiLo, &h, &board, &Hand::new() ).unwrap();
+        let high = enumerate( Variant::Omaha, &h, &board, &Hand::new() ).unwrap();
+        assert_eq!( hilo.equity(0), high.equity(0) );
+
+        // one player always takes the high half and the other the low, so
+        // every board pays the same and there is no spread to estimate
+        let h = hands(&["Kh Kc Qd Qh", "2c 3c 9h 9d"]);
+        let board: Hand = "4d 5h 8s Jc Qs".parse().unwrap();
+        let res = simulate( Variant::OmahaHiLo, &h, &board, &Hand::new(), 50, &mut rng ).unwrap();
+        assert_eq!( (res.equity(0), res.equity(1)), (0.5, 0.5) );
+        assert_eq!( (res.win(0), res.win(1)), (0.5, 0.5) );
+        assert_eq!( res.std_error(0), 0.0 );
+        assert_eq!( res.std_error(1), 0.0 );
+    }
+
+    #[test]
+    fn short_deck_equity() {
+        let mut rng = StdRng::seed_from_u64(17);
+        let h = hands(&["As Ks", "6c 6d"]);
+        let board: Hand = "7h 8h 10c".parse().unwrap();
+        // 36 cards less the seven seen
+        assert_eq!( board_count( Variant::ShortDeck, &h, &board, &Hand::new() ).unwrap(), 29 * 28 / 2 );
+        let res = calculate( Variant::ShortDeck, &h, &board, &Hand::new(), 1000, &mut rng ).unwrap();
+        assert!( res.exact );
+        assert!( (res.equity(0) + res.equity(1) - 1.0).abs() < 1e-9 );
+
+        assert_eq!( calculate( Variant::ShortDeck, &hands(&["As 2s", "6c 6d"]), &Hand::new(), &Hand::new(), 10, &mut rng ).err(),
+            Some(EquityError::NotInDeck("2s".parse().unwrap())) );
+    }
+
+    fn ranges(s: &[&str]) -> Vec<Range> {
+        s.iter().map(|r| r.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn range_versus_range_exact() {
+        let mut rng = StdRng::seed_from_u64(18);
+        let board: Hand = "2c 7d 9h Js".parse().unwrap();
+        let r = ranges(&["AA:0.5, KK", "QQ"]);
+        let res = range_equity( &r, &board, &Hand::new(), 10000, &mut rng ).unwrap();
+        assert!( res.overall.exact );
+        assert_eq!( res.combos[0].len(), 12 );
+        assert_eq!( res.combos[1].len(), 6 );
+
+        // the same as weighing up every pair of hands on its own
+        let (mut share, mut total) = (0.0, 0.0);
+        for a in r[0].combos() {
+            for b in r[1].combos() {
+                let e = enumerate( Variant::Holdem, &[a.hand.clone(), b.hand.clone()], &board, &Hand::new() ).unwrap();
+                share += a.weight * b.weight * e.equity(0) * e.boards;
+                total += a.weight * b.weight * e.boards;
+            }
+        }
+        assert!( (res.overall.equity(0) - share / total).abs() < 1e-9 );
+        assert!( (res.overall.equity(0) + res.overall.equity(1) - 1.0).abs() < 1e-9 );
+
+        let aces = res.combos[0].iter().find(|c| c.hand == "Ah As".parse().unwrap()).unwrap();
+        assert_eq!( aces.weight, 0.5 );
+        assert_eq!( aces.boards, 0.5 * 6.0 * 44.0 );
+        assert_eq!( aces.deals, 6 * 44 );
+        // every pair of overpairs against queens, whatever the weights
+        assert_eq!( res.overall.deals, 72 * 44 );
+        assert!( aces.equity() > 0.9 );
+        assert!( res.combos[1].iter().all(|c| c.equity() < 0.1) );
+    }
+
+    #[test]
+    fn range_versus_range_sampled() {
+        let board: Hand = "2c 7d 9h Js".parse().unwrap();
+        let r = ranges(&["AA:0.5, KK, AJs", "QQ, T8s"]);
+        let exact = range_equity( &r, &board, &Hand::new(), 100000, &mut StdRng::seed_from_u64(19) ).unwrap();
+        assert!( exact.overall.exact );
+        let sampled = range_equity( &r, &board, &Hand::new(), 5000, &mut StdRng::seed_from_u64(19) ).unwrap();
+        assert!( !sampled.overall.exact );
+        assert_eq!( sampled.overall.boards, 5000.0 );
+        assert!( (sampled.overall.equity(0) - exact.overall.equity(0)).abs() < 4.0 * sampled.overall.std_error(0) );
+        let dealt: f64 = sampled.combos[1].iter().map(|c| c.boards).sum();
+        assert_eq!( dealt, 5000.0 );
+
+        // every hand against every hand comes out even
+        let any = "22+, A2+, K2+, Q2+, J2+, T2+, 92+, 82+, 72+, 62+, 52+, 42+, 32";
+        let res = range_equity( &ranges(&[any, any]), &Hand::new(), &Hand::new(), 20000,
+                                &mut StdRng::seed_from_u64(20) ).unwrap();
+        assert_eq!( res.combos[0].len(), 1326 );
+        assert!( (res.overall.equity(0) - 0.5).abs() < 4.0 * res.overall.std_error(0) );
+    }
+
+    #[test]
+    fn range_versus_range_errors() {
+        let mut rng = StdRng::seed_from_u64(21);
+        let none = Hand::new();
+        assert_eq!( range_equity( &ranges(&["AA"]), &none, &none, 10, &mut rng ).err(),
+            Some(EquityError::TooFewPlayers) );
+        assert_eq!( range_equity( &ranges(&["KK", "AA"]), &"As Ah Ad".parse().unwrap(), &none, 10, &mut rng ).err(),
+            Some(EquityError::EmptyRange(1)) );
+        assert_eq!( range_equity( &ranges(&["AsKs", "AsKd"]), &none, &none, 10, &mut rng ).err(),
+            Some(EquityError::NoCompatibleHands) );
+        assert_eq!( range_equity( &ranges(&["KK", "AA"]), &"2c".parse().unwrap(), &"2c".parse().unwrap(), 10, &mut rng ).err(),
+            Some(EquityError::DuplicateCard("2c".parse().unwrap())) );
+    }
+}
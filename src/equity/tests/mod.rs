@@ -0,0 +1 @@
+pub mod equity_tests;
@@ -0,0 +1,344 @@
+use crate::card::{Card, CardRank, CardSuite, Hand, HandRank};
+use strum::IntoEnumIterator;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// A set of cards packed into a single integer, one bit per card.
+/// Each suite owns 16 bits, with bit `rank.index()` set for each card held.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub fn new() -> CardSet {
+        CardSet(0)
+    }
+
+    pub fn from_cards(cards: &[Card]) -> CardSet {
+        let mut set = CardSet::new();
+        for c in cards {
+            set.insert(c);
+        }
+        set
+    }
+
+    pub fn from_hand(h: &Hand) -> CardSet {
+        CardSet::from_cards(h.cards())
+    }
+
+    /// The card's bit in the set. Jokers have none, as `evaluate` only
+    /// knows the 13 ranks.
+    pub fn bit(card: &Card) -> u64 {
+        assert!( card.rank != CardRank::Joker, "a joker cannot go in a CardSet" );
+        1 << (card.suite.index() * 16 + card.rank.index())
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn insert(&mut self, card: &Card) {
+        self.0 |= CardSet::bit(card);
+    }
+
+    pub fn remove(&mut self, card: &Card) {
+        self.0 &= !CardSet::bit(card);
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.0 & CardSet::bit(card) != 0
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn union(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersects(&self, other: CardSet) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Ranks held in one suite, as a 13 bit mask.
+    pub fn suite_mask(&self, suite: &CardSuite) -> u16 {
+        ((self.0 >> (suite.index() * 16)) & 0x1fff) as u16
+    }
+
+    pub fn cards(&self) -> Vec<Card> {
+        let mut v = Vec::new();
+        for suite in CardSuite::iter() {
+            for rank in CardRank::iter() {
+                let c = Card { rank, suite: suite.clone() };
+                if self.contains(&c) {
+                    v.push(c);
+                }
+            }
+        }
+        v
+    }
+}
+
+const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+const WHEEL: u16 = 0b1_0000_0000_1111;
+
+// Number of distinct 5 card hand values in each HandRank, lowest rank first.
+const CLASS_COUNTS: [(HandRank, u16); 9] = [
+    (HandRank::HighCard, 1277),
+    (HandRank::Pair, 2860),
+    (HandRank::TwoPair, 858),
+    (HandRank::ThreeOfAKind, 858),
+    (HandRank::Straight, 10),
+    (HandRank::Flush, 1277),
+    (HandRank::FullHouse, 156),
+    (HandRank::FourOfAKind, 156),
+    (HandRank::StraightFlush, 10),
+];
+
+/// Number of distinct hand strengths; `evaluate` returns 1 to this value.
+pub const STRENGTH_COUNT: u16 = 7462;
+
+struct Tables {
+    // five distinct ranks of one suite, by rank mask
+    flush: Vec<u16>,
+    // five distinct ranks of mixed suites, by rank mask
+    unique: Vec<u16>,
+    // best straight contained in a rank mask, or 0
+    straight: Vec<u16>,
+    // hands with paired ranks, by product of rank primes
+    paired: HashMap<u32, u16>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+fn best_straight(mask: u16) -> u16 {
+    for high in (4..13).rev() {
+        let s = 0b11111 << (high - 4);
+        if mask & s == s {
+            return s;
+        }
+    }
+    if mask & WHEEL == WHEEL {
+        return WHEEL;
+    }
+    0
+}
+
+fn high_bit(mask: u16) -> u16 {
+    if mask == 0 {
+        0
+    } else {
+        1 << (15 - mask.leading_zeros())
+    }
+}
+
+// Keep the `n` highest ranks of a mask.
+fn top_bits(mut mask: u16, n: u32) -> u16 {
+    while mask.count_ones() > n {
+        mask &= mask - 1;
+    }
+    mask
+}
+
+fn mask_ranks(mask: u16) -> Vec<usize> {
+    (0..13).rev().filter(|r| mask & (1 << r) != 0).collect()
+}
+
+fn build_tables() -> Tables {
+    let mut straight = vec![0u16; 8192];
+    for (mask, s) in straight.iter_mut().enumerate() {
+        *s = best_straight(mask as u16);
+    }
+
+    // Every distinct 5 card hand value, keyed so that sorting puts them in
+    // strength order: the HandRank first, then the ranks that break ties.
+    enum Slot {
+        Flush(usize),
+        Unique(usize),
+        Paired(u32),
+    }
+    let mut classes: Vec<((HandRank, Vec<usize>), Slot)> = Vec::new();
+
+    for (mask, st) in straight.iter().enumerate() {
+        if (mask as u16).count_ones() != 5 {
+            continue;
+        }
+        let is_straight = *st == mask as u16;
+        let ranks = if mask as u16 == WHEEL {
+            vec![CardRank::Five.index()]
+        } else if is_straight {
+            vec![mask_ranks(mask as u16)[0]]
+        } else {
+            mask_ranks(mask as u16)
+        };
+        let (flush_rank, unique_rank) = if is_straight {
+            (HandRank::StraightFlush, HandRank::Straight)
+        } else {
+            (HandRank::Flush, HandRank::HighCard)
+        };
+        classes.push( ((flush_rank, ranks.clone()), Slot::Flush(mask)) );
+        classes.push( ((unique_rank, ranks), Slot::Unique(mask)) );
+    }
+
+    let mut counts = [0usize; 13];
+    paired_classes(0, 5, &mut counts, &mut classes, &|counts: &[usize; 13]| {
+        let mut groups: Vec<(usize, usize)> = (0..13)
+            .filter(|r| counts[*r] > 0)
+            .map(|r| (counts[r], r))
+            .collect();
+        groups.sort_by(|a, b| b.cmp(a));
+        let rank = match (groups[0].0, groups[1].0) {
+            (4, _) => HandRank::FourOfAKind,
+            (3, 2) => HandRank::FullHouse,
+            (3, _) => HandRank::ThreeOfAKind,
+            (2, 2) => HandRank::TwoPair,
+            _ => HandRank::Pair,
+        };
+        let product = (0..13).map(|r| PRIMES[r].pow(counts[r] as u32)).product();
+        ((rank, groups.iter().map(|g| g.1).collect()), Slot::Paired(product))
+    });
+
+    classes.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!( classes.len(), STRENGTH_COUNT as usize );
+
+    let mut t = Tables {
+        flush: vec![0; 8192],
+        unique: vec![0; 8192],
+        straight,
+        paired: HashMap::new(),
+    };
+    for (i, (_, slot)) in classes.iter().enumerate() {
+        let strength = i as u16 + 1;
+        match slot {
+            Slot::Flush(mask) => t.flush[*mask] = strength,
+            Slot::Unique(mask) => t.unique[*mask] = strength,
+            Slot::Paired(product) => {
+                t.paired.insert(*product, strength);
+            }
+        }
+    }
+    t
+}
+
+// Walk every multiset of `left` more ranks from `from` upwards, keeping the
+// ones that contain a pair.
+fn paired_classes<K, S>(from: usize, left: usize, counts: &mut [usize; 13],
+                        out: &mut Vec<(K, S)>, key: &dyn Fn(&[usize; 13]) -> (K, S)) {
+    if left == 0 {
+        if counts.iter().any(|c| *c > 1) {
+            out.push( key(counts) );
+        }
+        return;
+    }
+    for r in from..13 {
+        if counts[r] < 4 {
+            counts[r] += 1;
+            paired_classes(r, left - 1, counts, out, key);
+            counts[r] -= 1;
+        }
+    }
+}
+
+fn paired_strength(t: &Tables, ranks: &[(u16, u32)]) -> u16 {
+    let mut product = 1;
+    for (bit, n) in ranks {
+        product *= PRIMES[bit.trailing_zeros() as usize].pow(*n);
+    }
+    t.paired[&product]
+}
+
+/// Strength of the best 5 card hand within a set of 5 or more cards.
+/// Higher values are better hands, and equal values are exact ties.
+pub fn evaluate(cards: CardSet) -> u16 {
+    assert!( cards.count() >= 5 );
+    let t = tables();
+    let s: Vec<u16> = CardSuite::iter().map(|suite| cards.suite_mask(&suite)).collect();
+
+    let mut flush = 0;
+    for m in &s {
+        if m.count_ones() >= 5 {
+            let st = t.straight[*m as usize];
+            let v = if st != 0 {
+                t.flush[st as usize]
+            } else {
+                t.flush[top_bits(*m, 5) as usize]
+            };
+            flush = flush.max(v);
+        }
+    }
+    if hand_rank(flush) == HandRank::StraightFlush {
+        return flush;
+    }
+
+    let any = s[0] | s[1] | s[2] | s[3];
+    let two = (s[0] & s[1]) | (s[0] & s[2]) | (s[0] & s[3]) |
+              (s[1] & s[2]) | (s[1] & s[3]) | (s[2] & s[3]);
+    let three = (s[0] & s[1] & s[2]) | (s[0] & s[1] & s[3]) |
+                (s[0] & s[2] & s[3]) | (s[1] & s[2] & s[3]);
+    let four = s[0] & s[1] & s[2] & s[3];
+
+    if four != 0 {
+        let q = high_bit(four);
+        return paired_strength(t, &[(q, 4), (high_bit(any & !q), 1)]);
+    }
+    if three != 0 {
+        let r = high_bit(three);
+        let p = high_bit(two & !r);
+        if p != 0 {
+            return paired_strength(t, &[(r, 3), (p, 2)]);
+        }
+    }
+    if flush != 0 {
+        return flush;
+    }
+    let st = t.straight[any as usize];
+    if st != 0 {
+        return t.unique[st as usize];
+    }
+    if three != 0 {
+        let r = high_bit(three);
+        let k = top_bits(any & !r, 2);
+        return paired_strength(t, &[(r, 3), (high_bit(k), 1), (k & !high_bit(k), 1)]);
+    }
+    if two.count_ones() >= 2 {
+        let p1 = high_bit(two);
+        let p2 = high_bit(two & !p1);
+        return paired_strength(t, &[(p1, 2), (p2, 2), (high_bit(any & !p1 & !p2), 1)]);
+    }
+    if two != 0 {
+        let mut ranks = vec![(two, 2)];
+        let mut k = top_bits(any & !two, 3);
+        while k != 0 {
+            ranks.push( (high_bit(k), 1) );
+            k &= !high_bit(k);
+        }
+        return paired_strength(t, &ranks);
+    }
+    t.unique[top_bits(any, 5) as usize]
+}
+
+pub fn evaluate_cards(cards: &[Card]) -> u16 {
+    evaluate(CardSet::from_cards(cards))
+}
+
+pub fn evaluate_hand(h: &Hand) -> u16 {
+    evaluate(CardSet::from_hand(h))
+}
+
+/// The HandRank of a strength returned by `evaluate`.
+pub fn hand_rank(strength: u16) -> HandRank {
+    let mut upper = 0;
+    for (rank, count) in CLASS_COUNTS {
+        upper += count;
+        if strength <= upper {
+            return rank;
+        }
+    }
+    HandRank::StraightFlush
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::eval::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::cmp::Ordering;
+    use strum::IntoEnumIterator;
+
+    fn all_cards() -> Vec<Card> {
+        let mut v = Vec::new();
+        for suite in CardSuite::iter() {
+            for rank in CardRank::iter() {
+                v.push( Card { rank, suite: suite.clone() } );
+            }
+        }
+        v
+    }
+
+    fn eval_str(s: &str) -> u16 {
+        evaluate_hand( &s.parse::<Hand>().unwrap() )
+    }
+
+    #[test]
+    fn cardset_sanity() {
+        let h: Hand = "As Kd 2c".parse().unwrap();
+        let mut set = CardSet::from_hand(&h);
+        assert_eq!( set.count(), 3 );
+        assert!( set.contains(&"Kd".parse().unwrap()) );
+        assert!( !set.contains(&"Ks".parse().unwrap()) );
+        set.remove(&"Kd".parse().unwrap());
+        assert_eq!( set.count(), 2 );
+        set.insert(&"Kd".parse().unwrap());
+        assert_eq!( set, CardSet::from_hand(&h) );
+        assert_eq!( set.suite_mask(&CardSuite::Spades), 1 << CardRank::Ace.index() );
+        assert_eq!( set.cards().len(), 3 );
+
+        let all = CardSet::from_cards( &all_cards() );
+        assert_eq!( all.count(), 52 );
+        assert!( all.intersects(set) );
+        assert!( !CardSet::from_hand(&"Qh".parse().unwrap()).intersects(set) );
+    }
+
+    #[test]
+    #[should_panic(expected = "a joker cannot go in a CardSet")]
+    fn cardset_rejects_jokers() {
+        CardSet::from_hand( &"As Xd".parse().unwrap() );
+    }
+
+    #[test]
+    fn evaluate_known_hands() {
+        assert_eq!( eval_str("As Ks Qs Js 10s"), STRENGTH_COUNT );
+        assert_eq!( hand_rank(eval_str("As Ks Qs Js 10s")), HandRank::StraightFlush );
+        assert_eq!( eval_str("7h 5d 4c 3s 2h"), 1 );
+        assert_eq!( hand_rank(eval_str("Ah 2h 3h 4h 5h")), HandRank::StraightFlush );
+        assert_eq!( hand_rank(eval_str("Ah 2d 3h 4h 5h")), HandRank::Straight );
+        assert_eq!( hand_rank(eval_str("Ah Ad As Ac 5h")), HandRank::FourOfAKind );
+        assert_eq!( hand_rank(eval_str("Ah Ad As 5c 5h")), HandRank::FullHouse );
+        assert_eq!( hand_rank(eval_str("Ah 9h 7h 4h 2h")), HandRank::Flush );
+        assert_eq!( hand_rank(eval_str("Ah Ad As 6c 5h")), HandRank::ThreeOfAKind );
+        assert_eq!( hand_rank(eval_str("Ah Ad 6s 6c 5h")), HandRank::TwoPair );
+        assert_eq!( hand_rank(eval_str("Ah Ad 7s 6c 5h")), HandRank::Pair );
+        assert_eq!( hand_rank(eval_str("Ah Jd 7s 6c 5h")), HandRank::HighCard );
+
+        assert!( eval_str("6h 2d 3h 4h 5h") > eval_str("Ah 2d 3h 4h 5h") );
+        assert!( eval_str("Ah Ad 6s 6c 5h") > eval_str("Ah Ad 6s 6c 4h") );
+        assert!( eval_str("Ah Ad 6s 6c 5h") == eval_str("As Ac 6h 6d 5c") );
+    }
+
+    #[test]
+    fn evaluate_seven_cards() {
+        // flush with a straight flush hiding below the top card
+        assert_eq!( hand_rank(eval_str("Ah 9h 8h 7h 6h 5h Kd")), HandRank::StraightFlush );
+        assert!( eval_str("Ah 9h 8h 7h 6h 5h Kd") == eval_str("9h 8h 7h 6h 5h") );
+        // two trips make a full house with the higher set
+        assert!( eval_str("Kh Kd Ks 2c 2h 2d 3c") == eval_str("Kh Kd Ks 2c 2h") );
+        // three pairs use the best kicker
+        assert!( eval_str("Kh Kd 7s 7c 2h 2d Qc") == eval_str("Kh Kd 7s 7c Qc") );
+        // quads with a pair kicker
+        assert!( eval_str("9h 9d 9s 9c Ah Ad 3c") == eval_str("9h 9d 9s 9c As") );
+        // six cards
+        assert_eq!( hand_rank(eval_str("2c 3d 4h 5s 6c Kd")), HandRank::Straight );
+    }
+
+    #[test]
+    fn evaluate_all_five_card_hands() {
+        let cards = all_cards();
+        let mut counts = [0u32; 9];
+        let mut seen = vec![false; STRENGTH_COUNT as usize + 1];
+        for a in 0..52 {
+            for b in a+1..52 {
+                for c in b+1..52 {
+                    for d in c+1..52 {
+                        for e in d+1..52 {
+                            let set = CardSet::from_cards( &[cards[a].clone(), cards[b].clone(),
+                                cards[c].clone(), cards[d].clone(), cards[e].clone()] );
+                            let v = evaluate(set);
+                            seen[v as usize] = true;
+                            counts[hand_rank(v) as usize] += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!( counts, [1302540, 1098240, 123552, 54912, 10200, 5108, 3744, 624, 40] );
+        assert_eq!( seen.iter().filter(|s| **s).count(), STRENGTH_COUNT as usize );
+    }
+
+    #[test]
+    fn matches_handwithdata() {
+        let mut rng = StdRng::seed_from_u64(4);
+        for _ in 0..5000 {
+            let mut d = Deck::new();
+            d.shuffle_with(&mut rng);
+            let mut h1 = Hand::new();
+            let mut h2 = Hand::new();
+            for _ in 0..5 {
+                h1.add( d.draw().unwrap() );
+                h2.add( d.draw().unwrap() );
+            }
+            let w1 = HandWithData::from_hand(&h1);
+            let w2 = HandWithData::from_hand(&h2);
+            let v1 = evaluate_hand(&h1);
+            let v2 = evaluate_hand(&h2);
+            assert_eq!( hand_rank(v1), w1.rank() );
+            assert_eq!( v1.cmp(&v2), w1.cmp(&w2), "{} vs {}", h1, h2 );
+        }
+    }
+
+    #[test]
+    fn matches_find_best_hand() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..500 {
+            let mut d = Deck::new();
+            d.shuffle_with(&mut rng);
+            let mut h1 = Hand::new();
+            let mut h2 = Hand::new();
+            let mut board = Hand::new();
+            for _ in 0..2 {
+                h1.add( d.draw().unwrap() );
+                h2.add( d.draw().unwrap() );
+            }
+            for _ in 0..5 {
+                board.add( d.draw().unwrap() );
+            }
+            let c1 = [h1.cards(), board.cards()].concat();
+            let c2 = [h2.cards(), board.cards()].concat();
+            let v1 = evaluate_cards( &c1 );
+            let v2 = evaluate_cards( &c2 );
+            let w1 = find_best_hand( &c1, false ).data;
+            let w2 = find_best_hand( &c2, false ).data;
+            assert_eq!( hand_rank(v1), w1.rank() );
+            let expected = w1.cmp(&w2);
+            assert_eq!( v1.cmp(&v2), expected );
+            if expected == Ordering::Equal {
+                assert_eq!( v1, v2 );
+            }
+        }
+    }
+}
@@ -0,0 +1,568 @@
+use crate::card::{BestHand, Deck, Hand, HandWithData, find_best_hand};
+use crate::pot::{Distribution, PotAward, distribute};
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+    Showdown,
+}
+
+impl Street {
+    pub fn name(&self) -> String {
+        match self {
+            Street::Preflop  => String::from("Preflop"),
+            Street::Flop     => String::from("Flop"),
+            Street::Turn     => String::from("Turn"),
+            Street::River    => String::from("River"),
+            Street::Showdown => String::from("Showdown"),
+        }
+    }
+}
+
+/// A player's decision. Bet and raise amounts are the total the player
+/// will have in front of them on this street ("raise to"), not the increase.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Bet(u32),
+    Raise(u32),
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Fold => write!(f, "fold"),
+            Action::Check => write!(f, "check"),
+            Action::Call => write!(f, "call"),
+            Action::Bet(n) => write!(f, "bet {}", n),
+            Action::Raise(n) => write!(f, "raise to {}", n),
+        }
+    }
+}
+
+/// The actions open to the player whose turn it is. Folding is always
+/// allowed. Bet and raise ranges are inclusive totals for the street.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegalActions {
+    pub check: bool,
+    pub call: Option<u32>,
+    pub bet: Option<(u32, u32)>,
+    pub raise: Option<(u32, u32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableError {
+    TooFewPlayers,
+    TooManyPlayers,
+    InvalidBlinds,
+    HandInProgress,
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TableError::TooFewPlayers => write!(f, "at least two players with chips are needed"),
+            TableError::TooManyPlayers => write!(f, "too many players for one deck"),
+            TableError::InvalidBlinds => write!(f, "the big blind must be at least the small blind and above zero"),
+            TableError::HandInProgress => write!(f, "a hand is already being played"),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionError {
+    HandNotInProgress,
+    NotYourTurn { expected: usize },
+    CannotCheck { to_call: u32 },
+    NothingToCall,
+    CannotBet,
+    CannotRaise,
+    BelowMinimum { min: u32 },
+    AboveMaximum { max: u32 },
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ActionError::HandNotInProgress => write!(f, "no hand is being played"),
+            ActionError::NotYourTurn { expected } => write!(f, "it is seat {}'s turn", expected + 1),
+            ActionError::CannotCheck { to_call } => write!(f, "cannot check, {} to call", to_call),
+            ActionError::NothingToCall => write!(f, "there is nothing to call"),
+            ActionError::CannotBet => write!(f, "cannot bet, there is already a bet"),
+            ActionError::CannotRaise => write!(f, "raising is not allowed"),
+            ActionError::BelowMinimum { min } => write!(f, "must be at least {}", min),
+            ActionError::AboveMaximum { max } => write!(f, "can be at most {}", max),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TableConfig {
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub ante: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Seat {
+    pub stack: u32,
+    pub hole: Hand,
+    // chips put in on the current street, and over the whole hand
+    pub bet: u32,
+    pub contributed: u32,
+    pub dealt_in: bool,
+    pub folded: bool,
+    pub all_in: bool,
+}
+
+impl Seat {
+    fn new(stack: u32) -> Seat {
+        Seat { stack, hole: Hand::new(), bet: 0, contributed: 0,
+               dealt_in: false, folded: false, all_in: false }
+    }
+
+    // Still contesting the pot.
+    fn live(&self) -> bool {
+        self.dealt_in && !self.folded
+    }
+
+    // Still able to make decisions.
+    fn active(&self) -> bool {
+        self.live() && !self.all_in
+    }
+
+    fn pay(&mut self, amount: u32) -> u32 {
+        let paid = amount.min(self.stack);
+        self.stack -= paid;
+        self.bet += paid;
+        self.contributed += paid;
+        if self.stack == 0 {
+            self.all_in = true;
+        }
+        paid
+    }
+}
+
+/// What the player to act can see of the table.
+#[derive(Debug, Clone)]
+pub struct TableView {
+    pub seat: usize,
+    pub hole: Hand,
+    pub board: Hand,
+    pub street: Street,
+    pub button: usize,
+    pub big_blind: u32,
+    pub pot: u32,
+    pub stacks: Vec<u32>,
+    pub bets: Vec<u32>,
+    // players still contesting the pot
+    pub live: Vec<bool>,
+    pub legal: LegalActions,
+}
+
+impl TableView {
+    pub fn to_call(&self) -> u32 {
+        self.legal.call.unwrap_or(0)
+    }
+
+    pub fn opponents(&self) -> usize {
+        self.live.iter().filter(|l| **l).count() - 1
+    }
+}
+
+/// How a finished hand was settled.
+pub struct HandResult {
+    pub payouts: Vec<u32>,
+    pub pots: Vec<PotAward>,
+    pub returned: Option<(usize, u32)>,
+    // best hand of each player still in at showdown
+    pub shown: Vec<Option<BestHand>>,
+}
+
+/// A no-limit hold'em table, dealing one hand at a time and enforcing
+/// the order and size of each player's actions.
+pub struct Table {
+    config: TableConfig,
+    seats: Vec<Seat>,
+    button: usize,
+    deck: Deck,
+    board: Hand,
+    street: Street,
+    to_act: Option<usize>,
+    // highest bet on this street and the size of the last full raise
+    current_bet: u32,
+    min_raise: u32,
+    needs_action: Vec<bool>,
+    // the bet each player last acted facing on this street
+    acted_at: Vec<Option<u32>>,
+    hands_played: u32,
+    result: Option<HandResult>,
+}
+
+impl Table {
+    pub fn new(config: TableConfig, stacks: &[u32]) -> Result<Table, TableError> {
+        if config.big_blind == 0 || config.small_blind > config.big_blind {
+            return Err(TableError::InvalidBlinds);
+        }
+        if stacks.len() < 2 {
+            return Err(TableError::TooFewPlayers);
+        }
+        if stacks.len() * 2 + 5 > Deck::new().count() {
+            return Err(TableError::TooManyPlayers);
+        }
+        Ok(Table {
+            config,
+            seats: stacks.iter().map(|s| Seat::new(*s)).collect(),
+            button: stacks.len() - 1,
+            deck: Deck::new(),
+            board: Hand::new(),
+            street: Street::Showdown,
+            to_act: None,
+            current_bet: 0,
+            min_raise: config.big_blind,
+            needs_action: vec![false; stacks.len()],
+            acted_at: vec![None; stacks.len()],
+            hands_played: 0,
+            result: None,
+        })
+    }
+
+    pub fn config(&self) -> TableConfig {
+        self.config
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    pub fn button(&self) -> usize {
+        self.button
+    }
+
+    pub fn board(&self) -> &Hand {
+        &self.board
+    }
+
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    pub fn to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    pub fn hands_played(&self) -> u32 {
+        self.hands_played
+    }
+
+    pub fn in_progress(&self) -> bool {
+        self.street != Street::Showdown
+    }
+
+    /// Total chips put in by all players this hand.
+    pub fn pot(&self) -> u32 {
+        self.seats.iter().map(|s| s.contributed).sum()
+    }
+
+    pub fn current_bet(&self) -> u32 {
+        self.current_bet
+    }
+
+    /// Settlement of the last hand, once it is over.
+    pub fn result(&self) -> Option<&HandResult> {
+        self.result.as_ref()
+    }
+
+    /// Change a player's chips between hands, e.g. to rebuy.
+    pub fn set_stack(&mut self, seat: usize, stack: u32) -> Result<(), TableError> {
+        if self.in_progress() {
+            return Err(TableError::HandInProgress);
+        }
+        self.seats[seat].stack = stack;
+        Ok(())
+    }
+
+    /// The table as seen by the player whose turn it is.
+    pub fn view(&self) -> Option<TableView> {
+        let seat = self.to_act?;
+        Some(TableView {
+            seat,
+            hole: self.seats[seat].hole.clone(),
+            board: self.board.clone(),
+            street: self.street,
+            button: self.button,
+            big_blind: self.config.big_blind,
+            pot: self.pot(),
+            stacks: self.seats.iter().map(|s| s.stack).collect(),
+            bets: self.seats.iter().map(|s| s.bet).collect(),
+            live: self.seats.iter().map(|s| s.live()).collect(),
+            legal: self.legal_actions()?,
+        })
+    }
+
+    // Next seat after `from` (exclusive) that satisfies `pred`.
+    fn next_seat(&self, from: usize, pred: impl Fn(&Seat) -> bool) -> Option<usize> {
+        let n = self.seats.len();
+        (1..=n).map(|i| (from + i) % n).find(|i| pred(&self.seats[*i]))
+    }
+
+    /// Move the button, post antes and blinds and deal hole cards from
+    /// `deck`, which should already be shuffled.
+    pub fn start_hand(&mut self, deck: Deck) -> Result<(), TableError> {
+        if self.in_progress() {
+            return Err(TableError::HandInProgress);
+        }
+        if self.seats.iter().filter(|s| s.stack > 0).count() < 2 {
+            return Err(TableError::TooFewPlayers);
+        }
+        for s in self.seats.iter_mut() {
+            let stack = s.stack;
+            *s = Seat::new(stack);
+            s.dealt_in = stack > 0;
+        }
+        self.button = self.next_seat(self.button, |s| s.dealt_in).unwrap();
+        self.deck = deck;
+        self.board = Hand::new();
+        self.street = Street::Preflop;
+        self.result = None;
+        self.hands_played += 1;
+
+        let ante = self.config.ante;
+        for s in self.seats.iter_mut().filter(|s| s.dealt_in) {
+            s.pay(ante);
+            s.bet = 0;
+        }
+
+        // heads up, the button posts the small blind and acts first preflop
+        let players = self.seats.iter().filter(|s| s.dealt_in).count();
+        let sb = if players == 2 {
+            self.button
+        } else {
+            self.next_seat(self.button, |s| s.dealt_in).unwrap()
+        };
+        let bb = self.next_seat(sb, |s| s.dealt_in).unwrap();
+        self.seats[sb].pay(self.config.small_blind);
+        self.seats[bb].pay(self.config.big_blind);
+
+        let mut seat = self.button;
+        for _ in 0..2 {
+            for _ in 0..players {
+                seat = self.next_seat(seat, |s| s.dealt_in).unwrap();
+                let card = self.deck.draw().expect("deck ran out dealing hole cards");
+                self.seats[seat].hole.add( card );
+            }
+        }
+
+        self.current_bet = self.config.big_blind;
+        self.min_raise = self.config.big_blind;
+        self.start_round(bb);
+        Ok(())
+    }
+
+    // Open a betting round with the first player after `after` to act.
+    fn start_round(&mut self, after: usize) {
+        for i in 0..self.seats.len() {
+            self.needs_action[i] = self.seats[i].active();
+            self.acted_at[i] = None;
+        }
+        self.to_act = self.next_seat(after, |s| s.active());
+        self.advance();
+    }
+
+    /// Chips the given seat needs to put in to call.
+    pub fn to_call(&self, seat: usize) -> u32 {
+        let s = &self.seats[seat];
+        (self.current_bet - s.bet.min(self.current_bet)).min(s.stack)
+    }
+
+    /// The actions open to the player whose turn it is.
+    pub fn legal_actions(&self) -> Option<LegalActions> {
+        let seat = self.to_act?;
+        let s = &self.seats[seat];
+        let to_call = self.to_call(seat);
+        let max = s.bet + s.stack;
+        let mut legal = LegalActions { check: to_call == 0, call: None, bet: None, raise: None };
+        if to_call > 0 {
+            legal.call = Some(to_call);
+        }
+        if self.current_bet == 0 {
+            legal.bet = Some( (self.config.big_blind.min(max), max) );
+        } else if max > self.current_bet && self.may_raise(seat) {
+            legal.raise = Some( ((self.current_bet + self.min_raise).min(max), max) );
+        }
+        Some(legal)
+    }
+
+    // A player who has acted may only raise again if they have since
+    // faced at least a full raise.
+    fn may_raise(&self, seat: usize) -> bool {
+        match self.acted_at[seat] {
+            None => true,
+            Some(faced) => self.current_bet >= faced + self.min_raise,
+        }
+    }
+
+    /// Apply an action for `seat`, which must be the player to act.
+    pub fn act(&mut self, seat: usize, action: Action) -> Result<(), ActionError> {
+        let expected = self.to_act.ok_or(ActionError::HandNotInProgress)?;
+        if seat != expected {
+            return Err(ActionError::NotYourTurn { expected });
+        }
+        let legal = self.legal_actions().unwrap();
+        match action {
+            Action::Fold => {
+                self.seats[seat].folded = true;
+            }
+            Action::Check => {
+                if !legal.check {
+                    return Err(ActionError::CannotCheck { to_call: self.to_call(seat) });
+                }
+            }
+            Action::Call => {
+                let amount = legal.call.ok_or(ActionError::NothingToCall)?;
+                self.seats[seat].pay(amount);
+            }
+            Action::Bet(total) => {
+                let (min, max) = legal.bet.ok_or(ActionError::CannotBet)?;
+                self.wager(seat, total, min, max)?;
+            }
+            Action::Raise(total) => {
+                let (min, max) = legal.raise.ok_or(ActionError::CannotRaise)?;
+                self.wager(seat, total, min, max)?;
+            }
+        }
+        self.needs_action[seat] = false;
+        self.acted_at[seat] = Some(self.current_bet);
+        self.to_act = self.next_seat(seat, |s| s.active());
+        self.advance();
+        Ok(())
+    }
+
+    // Put a bet or raise to `total` in front of `seat`.
+    fn wager(&mut self, seat: usize, total: u32, min: u32, max: u32) -> Result<(), ActionError> {
+        if total > max {
+            return Err(ActionError::AboveMaximum { max });
+        }
+        if total < min {
+            return Err(ActionError::BelowMinimum { min });
+        }
+        let increase = total - self.current_bet;
+        // a short all-in does not change the size of the next raise
+        if increase >= self.min_raise {
+            self.min_raise = increase;
+        }
+        self.current_bet = total;
+        let s = &mut self.seats[seat];
+        s.pay(total - s.bet);
+        for i in 0..self.seats.len() {
+            if i != seat && self.seats[i].active() {
+                self.needs_action[i] = true;
+            }
+        }
+        Ok(())
+    }
+
+    // Move on to the next player, street or showdown as needed.
+    fn advance(&mut self) {
+        loop {
+            if self.seats.iter().filter(|s| s.live()).count() == 1 {
+                self.finish();
+                return;
+            }
+            // a lone player with nothing to call has no decision to make
+            let lone = self.seats.iter().filter(|s| s.active()).count() == 1;
+            let waiting = (0..self.seats.len())
+                .any(|i| self.needs_action[i] && self.seats[i].active() && !(lone && self.to_call(i) == 0));
+            if waiting {
+                while let Some(seat) = self.to_act {
+                    if self.needs_action[seat] && !(lone && self.to_call(seat) == 0) {
+                        return;
+                    }
+                    self.to_act = self.next_seat(seat, |s| s.active());
+                }
+            }
+            self.end_round();
+            if self.street == Street::Showdown {
+                self.finish();
+                return;
+            }
+            // betting continues only while two players can still act
+            if self.seats.iter().filter(|s| s.active()).count() >= 2 {
+                let button = self.button;
+                for i in 0..self.seats.len() {
+                    self.needs_action[i] = self.seats[i].active();
+                    self.acted_at[i] = None;
+                }
+                self.to_act = self.next_seat(button, |s| s.active());
+                if self.to_act.is_some() {
+                    return;
+                }
+            }
+        }
+    }
+
+    // Close the betting round and deal the next street.
+    fn end_round(&mut self) {
+        for s in self.seats.iter_mut() {
+            s.bet = 0;
+        }
+        self.current_bet = 0;
+        self.min_raise = self.config.big_blind;
+        self.to_act = None;
+        let (next, cards) = match self.street {
+            Street::Preflop => (Street::Flop, 3),
+            Street::Flop => (Street::Turn, 1),
+            Street::Turn => (Street::River, 1),
+            Street::River | Street::Showdown => (Street::Showdown, 0),
+        };
+        for _ in 0..cards {
+            let card = self.deck.draw().expect("deck ran out dealing the board");
+            self.board.add( card );
+        }
+        self.street = next;
+    }
+
+    // Settle the hand: the last player standing takes the pot, otherwise
+    // each main and side pot goes to the best hand eligible for it.
+    fn finish(&mut self) {
+        self.street = Street::Showdown;
+        self.to_act = None;
+        let n = self.seats.len();
+        let mut shown: Vec<Option<BestHand>> = (0..n).map(|_| None).collect();
+        let live: Vec<usize> = (0..n).filter(|i| self.seats[*i].live()).collect();
+        if live.len() > 1 {
+            while self.board.count() < 5 {
+                let card = self.deck.draw().expect("deck ran out dealing the board");
+                self.board.add( card );
+            }
+            for i in &live {
+                let cards = [self.seats[*i].hole.cards(), self.board.cards()].concat();
+                shown[*i] = Some(find_best_hand( &cards, false ));
+            }
+        }
+        let contributions: Vec<u32> = self.seats.iter().map(|s| s.contributed).collect();
+        let folded: Vec<bool> = self.seats.iter().map(|s| !s.live()).collect();
+        let hands: Vec<Option<&HandWithData>> = shown.iter().map(|b| b.as_ref().map(|b| &b.data)).collect();
+        // odd chips go to the first winners clockwise from the button
+        let order: Vec<usize> = (1..=n).map(|i| (self.button + i) % n).collect();
+        let dist = distribute(&contributions, &folded, &hands, &order);
+        for (s, p) in self.seats.iter_mut().zip(&dist.payouts) {
+            s.stack += p;
+        }
+        let Distribution { payouts, awards, returned } = dist;
+        self.result = Some(HandResult { payouts, pots: awards, returned, shown });
+    }
+}
+
+#[cfg(test)]
+mod tests;
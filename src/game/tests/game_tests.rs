@@ -0,0 +1,294 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::game::*;
+
+    const CONFIG: TableConfig = TableConfig { small_blind: 5, big_blind: 10, ante: 0 };
+
+    fn deck(s: &str) -> Deck {
+        s.parse().unwrap()
+    }
+
+    fn total_chips(t: &Table) -> u32 {
+        t.seats().iter().map(|s| s.stack).sum::<u32>() +
+            if t.in_progress() { t.pot() } else { 0 }
+    }
+
+    #[test]
+    fn table_errors() {
+        assert_eq!( Table::new( TableConfig { small_blind: 5, big_blind: 0, ante: 0 }, &[100, 100] ).err(),
+            Some(TableError::InvalidBlinds) );
+        assert_eq!( Table::new( TableConfig { small_blind: 20, big_blind: 10, ante: 0 }, &[100, 100] ).err(),
+            Some(TableError::InvalidBlinds) );
+        assert_eq!( Table::new( CONFIG, &[100] ).err(), Some(TableError::TooFewPlayers) );
+        assert_eq!( Table::new( CONFIG, &[100; 24] ).err(), Some(TableError::TooManyPlayers) );
+
+        let mut t = Table::new( CONFIG, &[100, 0, 0] ).unwrap();
+        assert_eq!( t.start_hand( Deck::new() ).err(), Some(TableError::TooFewPlayers) );
+
+        let mut t = Table::new( CONFIG, &[100, 100] ).unwrap();
+        t.start_hand( Deck::new() ).unwrap();
+        assert_eq!( t.start_hand( Deck::new() ).err(), Some(TableError::HandInProgress) );
+    }
+
+    #[test]
+    fn blinds_and_antes() {
+        let mut t = Table::new( TableConfig { small_blind: 5, big_blind: 10, ante: 1 }, &[1000, 1000, 1000] ).unwrap();
+        t.start_hand( Deck::new() ).unwrap();
+        assert_eq!( t.button(), 0 );
+        assert_eq!( t.street(), Street::Preflop );
+        assert_eq!( t.seats()[1].bet, 5 );
+        assert_eq!( t.seats()[2].bet, 10 );
+        assert_eq!( t.seats()[0].stack, 999 );
+        assert_eq!( t.seats()[2].stack, 989 );
+        assert_eq!( t.pot(), 18 );
+        assert!( t.seats().iter().all(|s| s.hole.count() == 2) );
+        assert_eq!( t.to_act(), Some(0) );
+        assert_eq!( t.legal_actions().unwrap(),
+            LegalActions { check: false, call: Some(10), bet: None, raise: Some((20, 999)) } );
+    }
+
+    #[test]
+    fn heads_up_order() {
+        let mut t = Table::new( CONFIG, &[1000, 1000] ).unwrap();
+        t.start_hand( Deck::new() ).unwrap();
+        // the button posts the small blind and acts first before the flop
+        assert_eq!( t.button(), 0 );
+        assert_eq!( t.seats()[0].bet, 5 );
+        assert_eq!( t.to_act(), Some(0) );
+        t.act( 0, Action::Call ).unwrap();
+        // the big blind has the option
+        assert_eq!( t.to_act(), Some(1) );
+        assert!( t.legal_actions().unwrap().check );
+        t.act( 1, Action::Check ).unwrap();
+        // and acts first after it
+        assert_eq!( t.street(), Street::Flop );
+        assert_eq!( t.board().count(), 3 );
+        assert_eq!( t.to_act(), Some(1) );
+        assert_eq!( t.legal_actions().unwrap(),
+            LegalActions { check: true, call: None, bet: Some((10, 990)), raise: None } );
+    }
+
+    #[test]
+    fn action_errors() {
+        let mut t = Table::new( CONFIG, &[1000, 1000, 1000] ).unwrap();
+        assert_eq!( t.act( 0, Action::Check ).err(), Some(ActionError::HandNotInProgress) );
+        t.start_hand( Deck::new() ).unwrap();
+        assert_eq!( t.act( 1, Action::Call ).err(), Some(ActionError::NotYourTurn { expected: 0 }) );
+        assert_eq!( t.act( 0, Action::Check ).err(), Some(ActionError::CannotCheck { to_call: 10 }) );
+        assert_eq!( t.act( 0, Action::Bet(20) ).err(), Some(ActionError::CannotBet) );
+        assert_eq!( t.act( 0, Action::Raise(15) ).err(), Some(ActionError::BelowMinimum { min: 20 }) );
+        assert_eq!( t.act( 0, Action::Raise(1001) ).err(), Some(ActionError::AboveMaximum { max: 1000 }) );
+        // nothing changed after the rejected actions
+        assert_eq!( t.to_act(), Some(0) );
+        assert_eq!( t.pot(), 15 );
+
+        t.act( 0, Action::Call ).unwrap();
+        t.act( 1, Action::Call ).unwrap();
+        t.act( 2, Action::Check ).unwrap();
+        assert_eq!( t.street(), Street::Flop );
+        assert_eq!( t.to_act(), Some(1) );
+        assert_eq!( t.act( 1, Action::Call ).err(), Some(ActionError::NothingToCall) );
+        assert_eq!( t.act( 1, Action::Raise(20) ).err(), Some(ActionError::CannotRaise) );
+        assert_eq!( t.act( 1, Action::Bet(5) ).err(), Some(ActionError::BelowMinimum { min: 10 }) );
+        t.act( 1, Action::Bet(10) ).unwrap();
+    }
+
+    #[test]
+    fn table_view() {
+        let mut t = Table::new( CONFIG, &[1000, 1000, 1000] ).unwrap();
+        assert!( t.view().is_none() );
+        t.start_hand( Deck::new() ).unwrap();
+        t.act( 0, Action::Raise(30) ).unwrap();
+        let v = t.view().unwrap();
+        assert_eq!( v.seat, 1 );
+        assert!( v.hole == t.seats()[1].hole );
+        assert_eq!( v.pot, 45 );
+        assert_eq!( v.bets, vec![30, 5, 10] );
+        assert_eq!( v.stacks, vec![970, 995, 990] );
+        assert_eq!( v.to_call(), 25 );
+        assert_eq!( v.opponents(), 2 );
+        assert_eq!( v.legal, t.legal_actions().unwrap() );
+
+        assert_eq!( t.set_stack( 0, 50 ).err(), Some(TableError::HandInProgress) );
+        t.act( 1, Action::Fold ).unwrap();
+        t.act( 2, Action::Fold ).unwrap();
+        t.set_stack( 0, 50 ).unwrap();
+        assert_eq!( t.seats()[0].stack, 50 );
+    }
+
+    #[test]
+    fn everyone_folds() {
+        let mut t = Table::new( CONFIG, &[1000, 1000, 1000] ).unwrap();
+        t.start_hand( Deck::new() ).unwrap();
+        t.act( 0, Action::Fold ).unwrap();
+        t.act( 1, Action::Fold ).unwrap();
+        assert!( !t.in_progress() );
+        assert_eq!( t.to_act(), None );
+        assert_eq!( t.board().count(), 0 );
+        assert_eq!( t.result().unwrap().payouts, vec![0, 0, 15] );
+        assert_eq!( t.seats()[2].stack, 1005 );
+        assert_eq!( t.seats()[1].stack, 995 );
+        assert_eq!( total_chips(&t), 3000 );
+
+        // the button moves on for the next hand
+        t.start_hand( Deck::new() ).unwrap();
+        assert_eq!( t.button(), 1 );
+        assert_eq!( t.hands_played(), 2 );
+        assert_eq!( t.to_act(), Some(1) );
+    }
+
+    #[test]
+    fn raise_sizes() {
+        let mut t = Table::new( CONFIG, &[1000, 1000, 1000] ).unwrap();
+        t.start_hand( Deck::new() ).unwrap();
+        t.act( 0, Action::Raise(30) ).unwrap();
+        // a raise of 20 means the next raise must be at least 20 more
+        assert_eq!( t.legal_actions().unwrap().raise, Some((50, 1000)) );
+        assert_eq!( t.legal_actions().unwrap().call, Some(25) );
+        t.act( 1, Action::Raise(100) ).unwrap();
+        assert_eq!( t.legal_actions().unwrap().raise, Some((170, 1000)) );
+        t.act( 2, Action::Fold ).unwrap();
+        t.act( 0, Action::Call ).unwrap();
+        assert_eq!( t.street(), Street::Flop );
+        assert_eq!( t.pot(), 210 );
+        assert_eq!( t.current_bet(), 0 );
+    }
+
+    #[test]
+    fn short_all_in_does_not_reopen() {
+        let mut t = Table::new( CONFIG, &[1000, 1000, 35] ).unwrap();
+        t.start_hand( Deck::new() ).unwrap();
+        t.act( 0, Action::Raise(30) ).unwrap();
+        t.act( 1, Action::Call ).unwrap();
+        // the big blind can only move all in for 5 more than the raise
+        assert_eq!( t.legal_actions().unwrap().raise, Some((35, 35)) );
+        t.act( 2, Action::Raise(35) ).unwrap();
+        assert!( t.seats()[2].all_in );
+        // the players who already acted may only call or fold
+        assert_eq!( t.legal_actions().unwrap(),
+            LegalActions { check: false, call: Some(5), bet: None, raise: None } );
+        assert_eq!( t.act( 0, Action::Raise(100) ).err(), Some(ActionError::CannotRaise) );
+        t.act( 0, Action::Call ).unwrap();
+        t.act( 1, Action::Call ).unwrap();
+        assert_eq!( t.street(), Street::Flop );
+        // betting goes on between the two players left with chips
+        assert_eq!( t.to_act(), Some(1) );
+    }
+
+    #[test]
+    fn showdown() {
+        // heads up: seat 1 is dealt first, then the button
+        let mut t = Table::new( CONFIG, &[1000, 1000] ).unwrap();
+        t.start_hand( deck("As 2c Ah 7d Kd Ks 3h 8c Qd") ).unwrap();
+        assert_eq!( t.seats()[0].hole.to_string(), "2♣ 7♦" );
+        assert_eq!( t.seats()[1].hole.to_string(), "A♠ A♥" );
+        t.act( 0, Action::Call ).unwrap();
+        t.act( 1, Action::Check ).unwrap();
+        for _ in 0..3 {
+            t.act( 1, Action::Bet(20) ).unwrap();
+            t.act( 0, Action::Call ).unwrap();
+        }
+        assert!( !t.in_progress() );
+        assert_eq!( t.board().to_string(), "K♦ K♠ 3♥ 8♣ Q♦" );
+        let res = t.result().unwrap();
+        assert_eq!( res.payouts, vec![0, 140] );
+        assert!( res.shown[1].as_ref().unwrap().data.rank() == HandRank::TwoPair );
+        assert_eq!( t.seats()[0].stack, 930 );
+        assert_eq!( t.seats()[1].stack, 1070 );
+    }
+
+    #[test]
+    fn all_in_runs_out_the_board() {
+        let mut t = Table::new( CONFIG, &[500, 500] ).unwrap();
+        t.start_hand( deck("As 2c Ah 7d Kd Ks 3h 8c 2d") ).unwrap();
+        t.act( 0, Action::Raise(500) ).unwrap();
+        t.act( 1, Action::Call ).unwrap();
+        assert!( !t.in_progress() );
+        assert_eq!( t.board().count(), 5 );
+        assert_eq!( t.result().unwrap().payouts, vec![0, 1000] );
+        assert_eq!( t.seats()[0].stack, 0 );
+
+        // a busted player is skipped when dealing the next hand
+        let mut t = Table::new( CONFIG, &[500, 0, 500] ).unwrap();
+        t.start_hand( Deck::new() ).unwrap();
+        assert!( !t.seats()[1].dealt_in );
+        assert_eq!( t.seats()[1].hole.count(), 0 );
+        assert_eq!( t.to_act(), Some(0) );
+    }
+
+    #[test]
+    fn side_pots() {
+        let mut t = Table::new( CONFIG, &[1000, 1000, 50] ).unwrap();
+        t.start_hand( deck("7d As Ks 2c Ah Kh 3h 8c 9d Js 4s") ).unwrap();
+        assert_eq!( t.seats()[2].hole.to_string(), "A♠ A♥" );
+        t.act( 0, Action::Raise(200) ).unwrap();
+        t.act( 1, Action::Call ).unwrap();
+        t.act( 2, Action::Call ).unwrap();
+        assert!( t.seats()[2].all_in );
+        for _ in 0..3 {
+            t.act( 1, Action::Check ).unwrap();
+            t.act( 0, Action::Check ).unwrap();
+        }
+        assert!( !t.in_progress() );
+        // the short stack's aces take the main pot, the kings the side pot
+        let res = t.result().unwrap();
+        assert_eq!( res.payouts, vec![300, 0, 150] );
+        assert_eq!( res.pots.len(), 2 );
+        assert_eq!( res.pots[0].pot.eligible, vec![0, 1, 2] );
+        assert_eq!( res.pots[1].winners, vec![0] );
+        assert_eq!( total_chips(&t), 2050 );
+    }
+
+    #[test]
+    fn split_pot_odd_chip() {
+        // everyone plays the board, a straight
+        let mut t = Table::new( TableConfig { small_blind: 5, big_blind: 10, ante: 1 }, &[100, 100, 100] ).unwrap();
+        t.start_hand( deck("2c 3c 4c 2d 3d 4d 10h Jh Qs Kd Ac") ).unwrap();
+        t.act( 0, Action::Call ).unwrap();
+        t.act( 1, Action::Call ).unwrap();
+        t.act( 2, Action::Raise(20) ).unwrap();
+        t.act( 0, Action::Call ).unwrap();
+        t.act( 1, Action::Fold ).unwrap();
+        for _ in 0..3 {
+            t.act( 2, Action::Check ).unwrap();
+            t.act( 0, Action::Check ).unwrap();
+        }
+        assert!( !t.in_progress() );
+        // 53 chips: seat 2 is first after the button and gets the odd one
+        assert_eq!( t.result().unwrap().payouts, vec![26, 0, 27] );
+        assert_eq!( total_chips(&t), 300 );
+    }
+
+    #[test]
+    fn chips_are_conserved() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let mut rng = StdRng::seed_from_u64(21);
+        let mut t = Table::new( CONFIG, &[300, 300, 300, 300] ).unwrap();
+        let mut hands = 0;
+        while hands < 50 && t.start_hand( {
+            let mut d = Deck::new();
+            d.shuffle_with(&mut rng);
+            d
+        } ).is_ok() {
+            while let Some(seat) = t.to_act() {
+                let legal = t.legal_actions().unwrap();
+                // alternate between aggressive and passive lines
+                let action = match (hands + seat) % 3 {
+                    0 if legal.raise.is_some() => Action::Raise(legal.raise.unwrap().0),
+                    0 if legal.bet.is_some() => Action::Bet(legal.bet.unwrap().1),
+                    1 if legal.check => Action::Check,
+                    1 | 0 => Action::Call,
+                    _ => Action::Fold,
+                };
+                let action = if action == Action::Call && legal.call.is_none() { Action::Check } else { action };
+                t.act( seat, action ).unwrap();
+                assert_eq!( total_chips(&t), 1200 );
+            }
+            hands += 1;
+        }
+        assert!( hands > 1 );
+    }
+}
@@ -0,0 +1 @@
+pub mod game_tests;
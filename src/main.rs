@@ -1,11 +1,30 @@
 pub mod card;
-use std::cmp::Ordering;
-use clap::Parser;
+pub mod eval;
+pub mod equity;
+pub mod game;
+pub mod pot;
+pub mod agent;
+pub mod play;
+pub mod ranking;
+pub mod stud;
+pub mod draw;
+pub mod range;
+pub mod preflop;
+pub mod canonical;
+pub mod outs;
+pub mod strength;
+use clap::{Parser, Subcommand};
+use rand::{thread_rng, Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::path::PathBuf;
 
 /// Generate Texas Hold'em Poker hands and check for winner.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Number of hands to generate
     #[arg(short, long, default_value_t = 1)]
     count: u32,
@@ -13,29 +32,293 @@ struct Args {
     #[arg(short, long, default_value_t = false)]
     verbose: bool,
 
-   //TODO vary number of players
+    /// Number of jokers to shuffle into the deck; jokers are always wild
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=2))]
+    jokers: u8,
+
+    /// Ranks that are wild as well as jokers, e.g. "2" for deuces wild
+    #[arg(short, long, default_value = "")]
+    wild: card::WildCards,
+
+    /// Seed for shuffling, to reproduce a previous run
+    #[arg(short, long, global = true)]
+    seed: Option<u64>,
+
+    /// Game to deal: holdem, short-deck, omaha, omaha5, omaha6 or omaha-hilo
+    #[arg(short, long, default_value = "holdem", global = true)]
+    game: card::Variant,
+
+    /// Number of players at the table, 2 if not given, when dealing hands,
+    /// stud or draw
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(2..=10), global = true)]
+    players: Option<u8>,
+}
+
+impl Args {
+    fn players(&self) -> usize {
+        self.players.unwrap_or(2) as usize
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Estimate each player's chance of winning from their hole cards
+    Equity {
+        /// Hole cards for each player, e.g. "AsKs" "QdQc"
+        #[arg(required = true, num_args = 2..)]
+        hands: Vec<card::Hand>,
+
+        /// Cards already on the board, e.g. "Jh Ts 2c"
+        #[arg(short, long, default_value = "")]
+        board: card::Hand,
+
+        /// Cards known to be out of play
+        #[arg(short, long, default_value = "")]
+        dead: card::Hand,
+
+        /// Number of random boards to deal; every board is dealt instead
+        /// when there are no more than this
+        #[arg(short, long, default_value_t = 10000, value_parser = clap::value_parser!(u32).range(1..))]
+        trials: u32,
+    },
+    /// Work out the equity of hold'em ranges against each other
+    Ranges {
+        /// A range for each player, e.g. "QQ+, AKs" "JJ-99, AQs+"
+        #[arg(required = true, num_args = 2..)]
+        ranges: Vec<range::Range>,
+
+        /// Cards already on the board, e.g. "Jh Ts 2c"
+        #[arg(short, long, default_value = "")]
+        board: card::Hand,
+
+        /// Cards known to be out of play
+        #[arg(short, long, default_value = "")]
+        dead: card::Hand,
+
+        /// Number of random deals; every deal is made instead when there
+        /// are no more than this
+        #[arg(short, long, default_value_t = 100000, value_parser = clap::value_parser!(u32).range(1..))]
+        trials: u32,
+    },
+    /// Expand a range such as "QQ+, AKs, A5s-A2s" into its combos
+    Range {
+        /// The range, e.g. "QQ+, AKs, A5s-A2s, KQo, 76s, AA:0.5"
+        range: range::Range,
+
+        /// Cards known to be out of play, whose combos are removed
+        #[arg(short, long, default_value = "")]
+        dead: card::Hand,
+    },
+    /// Work out hand strength and potential after the flop against one
+    /// opponent
+    Strength {
+        /// Hole cards, e.g. "Ad Qc"
+        hole: card::Hand,
+
+        /// The flop, turn or river, e.g. "3h 4c Jh"
+        #[arg(short, long)]
+        board: card::Hand,
+
+        /// The opponent's range, e.g. "QQ+, AKs"; any two cards if not given
+        #[arg(short, long)]
+        range: Option<range::Range>,
+
+        /// Number of random deals; every opponent hand and board is counted
+        /// instead when there are no more than this
+        #[arg(short, long, default_value_t = 100000, value_parser = clap::value_parser!(u32).range(1..))]
+        trials: u32,
+    },
+    /// List the cards that would help hold'em hole cards on the flop or turn
+    Outs {
+        /// Hole cards, e.g. "Ah Kh"
+        hole: card::Hand,
+
+        /// Opponents' hole cards, if known; outs are then the cards that
+        /// improve the hand or take it from losing to winning
+        opponents: Vec<card::Hand>,
+
+        /// The flop or turn, e.g. "Qh 7h 2c"
+        #[arg(short, long)]
+        board: card::Hand,
+    },
+    /// Show the hand every suit swap of some hold'em cards comes down to,
+    /// and its index among all such hands
+    Canonical {
+        /// Hole cards, e.g. "Kd Ad"
+        hole: card::Hand,
+
+        /// Cards on the board: none, the flop, turn or river
+        #[arg(short, long, default_value = "")]
+        board: card::Hand,
+    },
+    /// Deal seven card stud, street by street
+    Stud {
+        /// Game to deal: stud, stud-hilo or razz
+        #[arg(default_value = "stud", value_name = "GAME")]
+        kind: stud::StudGame,
+    },
+    /// Deal five card draw, with every player drawing by standard strategy
+    Draw {
+        /// Instead of showing deals, simulate this many and report how
+        /// often players stand pat or draw each number of cards
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        simulate: Option<u32>,
+    },
+    /// Play no-limit hold'em against bots, typing your actions at the prompt
+    Play {
+        /// Number of bots at the table
+        #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=9))]
+        opponents: u8,
+
+        /// Chips each player starts with
+        #[arg(long, default_value_t = 1000)]
+        stack: u32,
+
+        /// Size of the big blind; the small blind is half of it
+        #[arg(short, long, default_value_t = 10)]
+        big_blind: u32,
+
+        /// Preflop equity table, written by the preflop subcommand, for
+        /// the bots to look hands up in
+        #[arg(long, value_name = "FILE")]
+        preflop: Option<PathBuf>,
+    },
+    /// Work out the equity of all 169 starting hands against random hands
+    /// and write it out as a table
+    Preflop {
+        /// Most opponents to work out equity against; the table has a
+        /// column for each number from 1 up to this
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=9))]
+        opponents: u8,
+
+        /// Number of random deals for each hand and number of opponents,
+        /// at least 100 so every hand gets a usable estimate
+        #[arg(short, long, default_value_t = 10000, value_parser = clap::value_parser!(u32).range(100..))]
+        trials: u32,
+
+        /// File to write the table to, as CSV, instead of the terminal
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
 }
 
 fn main() {
     let args = Args::parse();
+    if args.command.is_some() && (args.jokers > 0 || !args.wild.ranks.is_empty()) {
+        eprintln!("error: --jokers and --wild only apply to dealing hands, not to subcommands");
+        std::process::exit(1);
+    }
+    let deals = matches!(args.command, None | Some(Command::Stud { .. }) | Some(Command::Draw { .. }));
+    if args.players.is_some() && !deals {
+        eprintln!("error: --players only applies to dealing hands, stud and draw");
+        std::process::exit(1);
+    }
+
+    let seed = match args.seed {
+        Some(seed) => seed,
+        None => {
+            let seed = thread_rng().gen();
+            eprintln!("Seed: {}", seed);
+            seed
+        }
+    };
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    match &args.command {
+        None => deal(&args, &mut rng),
+        Some(Command::Equity { hands, board, dead, trials }) =>
+            show_equity(args.game, hands, board, dead, *trials, &mut rng),
+        Some(Command::Ranges { ranges, board, dead, trials }) => {
+            require_holdem(args.game, "ranges");
+            show_range_equity(ranges, board, dead, *trials, &mut rng)
+        }
+        Some(Command::Range { range, dead }) => {
+            require_holdem(args.game, "range");
+            show_range(range, dead)
+        }
+        Some(Command::Strength { hole, board, range, trials }) => {
+            require_holdem(args.game, "strength");
+            show_strength(hole, board, range.as_ref(), *trials, &mut rng)
+        }
+        Some(Command::Outs { hole, opponents, board }) => {
+            require_holdem(args.game, "outs");
+            show_outs(hole, opponents, board)
+        }
+        Some(Command::Canonical { hole, board }) => {
+            require_holdem(args.game, "canonical");
+            show_canonical(hole, board)
+        }
+        Some(Command::Stud { kind }) => {
+            if args.game != card::Variant::Holdem {
+                eprintln!("error: --game does not apply to stud; name the game after it, e.g. \"stud razz\"");
+                std::process::exit(1);
+            }
+            deal_stud(*kind, &args, &mut rng)
+        }
+        Some(Command::Draw { simulate }) => {
+            if args.game != card::Variant::Holdem {
+                eprintln!("error: --game does not apply to draw, which always deals five card draw");
+                std::process::exit(1);
+            }
+            match simulate {
+                Some(deals) => draw_stats(args.players(), *deals, &mut rng),
+                None => deal_draw(&args, &mut rng),
+            }
+        }
+        Some(Command::Preflop { opponents, trials, output }) => {
+            require_holdem(args.game, "preflop");
+            write_preflop_table(*opponents as usize, *trials, output.as_deref(), &mut rng)
+        }
+        Some(Command::Play { opponents, stack, big_blind, preflop }) => {
+            if args.game != card::Variant::Holdem {
+                eprintln!("error: only hold'em can be played against the bots");
+                std::process::exit(1);
+            }
+            let table = preflop.as_deref().map(|path| match preflop::PreflopTable::load(path) {
+                Ok(table) => table,
+                Err(e) => {
+                    eprintln!("error: cannot read {}: {}", path.display(), e);
+                    std::process::exit(1);
+                }
+            });
+            play(*opponents as usize, *stack, *big_blind, table, &mut rng)
+        }
+    }
+}
+
+// Exit with an error unless the game is hold'em, for subcommands that
+// know no other game.
+fn require_holdem(game: card::Variant, command: &str) {
+    if game != card::Variant::Holdem {
+        eprintln!("error: {} only supports holdem", command);
+        std::process::exit(1);
+    }
+}
+
+fn deal(args: &Args, rng: &mut StdRng) {
+    let players = args.players();
+    let hole = args.game.hole_cards();
+    if players * hole + 5 > args.game.deck().count() + args.jokers as usize {
+        eprintln!("error: not enough cards for {} players", players);
+        std::process::exit(1);
+    }
 
     for n in 0..args.count {
         if args.count > 1 {
             println!("Deal {}", n+1);
         }
 
-        let mut d = card::Deck::new();
-        d.shuffle();
+        let mut d = args.game.deck();
+        d.add_jokers(args.jokers as usize);
+        d.shuffle_with(rng);
 
-        // draw hands
-        let mut h1 = card::Hand::new();
-        let mut h2 = card::Hand::new();
-        for _ in 0..2 {
-            if let Some(card) = d.draw() {
-                h1.add( card );
-            }
-            if let Some(card) = d.draw() {
-                h2.add( card );
+        // draw hands, one card at a time to each player in turn
+        let mut hands = vec![card::Hand::new(); players];
+        for _ in 0..hole {
+            for h in hands.iter_mut() {
+                if let Some(card) = d.draw() {
+                    h.add( card );
+                }
             }
         }
 
@@ -47,19 +330,360 @@ fn main() {
             }
         }
 
-        println!("Player 1: {}", h1.to_string());
-        println!("Player 2: {}", h2.to_string());
-        println!("Dealer: {}", hd.to_string());
+        for (i, h) in hands.iter().enumerate() {
+            println!("Player {}: {}", i+1, h);
+        }
+        println!("Dealer: {}", hd);
+
+        let mut shown = Vec::new();
+        for (i, h) in hands.iter().enumerate() {
+            let best = args.game.best_wild_hand( h.cards(), hd.cards(), &args.wild, args.verbose );
+            println!("  Best hand for Player {}: {}", i+1, best.data);
+            shown.push(Some(best.data));
+        }
+        if args.game.hi_lo() {
+            let mut lows = Vec::new();
+            for (i, h) in hands.iter().enumerate() {
+                let low = args.game.best_low( h.cards(), hd.cards() ).map(|b| b.data);
+                match &low {
+                    Some(low) => println!("  Best low for Player {}: {}", i+1, low),
+                    None => println!("  No low for Player {}", i+1),
+                }
+                lows.push(low);
+            }
+            let highs = card::winners(&shown);
+            let low = if lows.iter().any(|l| l.is_some()) { Some(card::winners(&lows)) } else { None };
+            println!("{}", announce_hi_lo(&highs, low.as_deref()));
+        } else {
+            println!("{}", announce(&card::winners(&shown)));
+        }
+    }
+}
+
+// "Player 2" or "Players 1, 2 and 3".
+fn player_list(players: &[usize]) -> String {
+    let names: Vec<String> = players.iter().map(|w| (w + 1).to_string()).collect();
+    match names.len() {
+        1 => format!("Player {}", names[0]),
+        n => format!("Players {} and {}", names[..n-1].join(", "), names[n-1]),
+    }
+}
+
+// Describe who won each half of a split pot, and who scooped or was
+// quartered. `low` is None when nobody made a qualifying low.
+fn announce_hi_lo(high: &[usize], low: Option<&[usize]>) -> String {
+    if high.len() == 1 && low.is_none_or(|l| l == high) {
+        return format!("Player {} scoops the pot.", high[0] + 1);
+    }
+    let half = |winners: &[usize], name: &str| -> String {
+        let verb = if winners.len() == 1 { "wins" } else { "split" };
+        format!("{} {} {}", player_list(winners), verb, name)
+    };
+    let low = match low {
+        Some(low) => low,
+        None => return format!("{}, no qualifying low.", half(high, "high")),
+    };
+    // a two way tie for one half with no share of the other
+    let mut quartered: Vec<usize> = [(high, low), (low, high)].iter()
+        .filter(|(half, _)| half.len() == 2)
+        .flat_map(|(half, other)| half.iter().filter(|w| !other.contains(w)))
+        .copied()
+        .collect();
+    quartered.sort();
+    if quartered.is_empty() {
+        format!("{}, {}.", half(high, "high"), half(low, "low"))
+    } else {
+        format!("{}, {}. Quartered: {}.", half(high, "high"), half(low, "low"), player_list(&quartered))
+    }
+}
+
+fn announce(winners: &[usize]) -> String {
+    if winners.len() == 1 {
+        format!("{} wins.", player_list(winners))
+    } else {
+        format!("TIED: {} split the pot.", player_list(winners))
+    }
+}
+
+fn show_range_equity(ranges: &[range::Range], board: &card::Hand, dead: &card::Hand,
+                     trials: u32, rng: &mut StdRng) {
+    let res = match equity::range_equity(ranges, board, dead, trials, rng) {
+        Ok(res) => res,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let all = &res.overall;
+    if board.count() > 0 {
+        println!("Board: {}", board);
+    }
+    if all.exact {
+        println!("Deals enumerated: {}", all.deals);
+    } else {
+        println!("Deals made: {}", all.deals);
+    }
+    for (i, r) in ranges.iter().enumerate() {
+        println!("Player {}: {}  win {:.2}%  tie {:.2}%  lose {:.2}%  equity {:.2}% \u{b1} {:.2}%",
+            i+1, r, 100.0 * all.win(i), 100.0 * all.tie(i), 100.0 * all.loss(i),
+            100.0 * all.equity(i), 100.0 * all.std_error(i));
+    }
+    for (i, combos) in res.combos.iter().enumerate() {
+        println!("Player {} by hand:", i+1);
+        let mut dealt: Vec<&equity::ComboEquity> = combos.iter().filter(|c| c.deals > 0).collect();
+        dealt.sort_by(|a, b| b.equity().total_cmp(&a.equity()));
+        for c in dealt {
+            println!("  {}  equity {:.2}%  deals {}", c.hand, 100.0 * c.equity(), c.deals);
+        }
+    }
+}
+
+fn show_range(range: &range::Range, dead: &card::Hand) {
+    let mut range = range.clone();
+    range.remove_blocked( dead.cards() );
+    println!("Range: {}", range);
+    println!("Combos: {} (weighted {:.2})", range.count(), range.total_weight());
+    let combos: Vec<String> = range.combos().iter()
+        .map(|c| if c.weight == 1.0 { c.hand.to_string() } else { format!("{}:{}", c.hand, c.weight) })
+        .collect();
+    println!("{}", combos.join(", "));
+}
+
+fn show_strength(hole: &card::Hand, board: &card::Hand, range: Option<&range::Range>,
+                 trials: u32, rng: &mut StdRng) {
+    let s = match strength::calculate(hole, board, range, trials, rng) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("Hand: {}  Board: {}", hole, board);
+    match range {
+        Some(range) => println!("Against: {}", range),
+        None => println!("Against: any two cards"),
+    }
+    println!("{}", if s.exact { "Counted exactly" } else { "Estimated by sampling" });
+    println!("HS {:.3}  PPot {:.3}  NPot {:.3}  EHS {:.3}", s.hs, s.ppot, s.npot, s.ehs());
+}
+
+fn show_outs(hole: &card::Hand, opponents: &[card::Hand], board: &card::Hand) {
+    let outs = match outs::find_outs(hole, board, opponents) {
+        Ok(outs) => outs,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("Hand: {}  Board: {}  ({})", hole, board, outs.current.name());
+    if !opponents.is_empty() {
+        let hands: Vec<String> = opponents.iter().map(|h| h.to_string()).collect();
+        println!("Against: {}", hands.join(", "));
+    }
+    println!("Outs: {} ({} clean)", outs.count(), outs.clean());
+    for (kind, group) in outs.groups() {
+        let cards: Vec<String> = group.iter()
+            .map(|o| if o.clean { o.card.to_string() } else { format!("{}*", o.card) })
+            .collect();
+        println!("  {}: {}", kind, cards.join(" "));
+    }
+    if outs.clean() < outs.count() {
+        println!("* tainted: may not leave the hand best");
+    }
+}
+
+fn show_canonical(hole: &card::Hand, board: &card::Hand) {
+    if ![0, 3, 4, 5].contains(&board.cards().len()) {
+        eprintln!("error: a board has 0, 3, 4 or 5 cards, not {}", board.cards().len());
+        std::process::exit(1);
+    }
+    let indexer = canonical::HandIndexer::holdem(board.cards().len());
+    let cards = [hole.cards(), board.cards()].concat();
+    let (index, hand) = match (indexer.index(&cards), indexer.canonical(&cards)) {
+        (Ok(index), Ok(hand)) => (index, hand),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let (hole, board) = hand.cards().split_at(2);
+    let names = |cards: &[card::Card]| cards.iter().map(|c| c.name()).collect::<Vec<String>>().join(" ");
+    println!("Hole: {}", names(hole));
+    if !board.is_empty() {
+        println!("Board: {}", names(board));
+    }
+    println!("Index: {} of {}", index, indexer.size());
+}
+
+fn deal_stud(game: stud::StudGame, args: &Args, rng: &mut StdRng) {
+    let players = args.players();
+    for n in 0..args.count {
+        if args.count > 1 {
+            println!("Deal {}", n+1);
+        }
+
+        let mut d = card::Deck::new();
+        d.shuffle_with(rng);
+        let mut deal = match stud::StudDeal::new(game, players, d) {
+            Ok(deal) => deal,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        let bring_in = deal.bring_in();
+        loop {
+            println!("Street {}:", deal.street());
+            for (i, h) in deal.hands().iter().enumerate() {
+                println!("  Player {}: {}", i+1, h);
+            }
+            if let Some(card) = deal.common() {
+                println!("  Common card: {}", card);
+            }
+            if deal.street() == 3 {
+                println!("Player {} brings in with door card {}.", bring_in+1, deal.hands()[bring_in].door_card());
+            }
+            if !deal.next_street() {
+                break;
+            }
+        }
+
+        for i in 0..players {
+            println!("  Best hand for Player {}: {}", i+1, deal.describe(i));
+        }
+        match deal.showdown() {
+            stud::Showdown::High(winners) | stud::Showdown::Low(winners) => println!("{}", announce(&winners)),
+            stud::Showdown::HiLo { high, low } => println!("{}", announce_hi_lo(&high, low.as_deref())),
+        }
+    }
+}
+
+fn deal_draw(args: &Args, rng: &mut StdRng) {
+    let players = args.players();
+    for n in 0..args.count {
+        if args.count > 1 {
+            println!("Deal {}", n+1);
+        }
 
-        let show1 = card::find_best_hand( h1, hd.clone(), args.verbose );
-        println!("  Best hand for Player 1: {}", show1.to_string());
-        let show2 = card::find_best_hand( h2, hd, args.verbose );
-        println!("  Best hand for Player 2: {}", show2.to_string());
-        let res = match show1.cmp(&show2) {
-            Ordering::Greater => String::from("Player 1 wins."),
-            Ordering::Equal   => String::from("TIED"),
-            Ordering::Less    => String::from("Player 2 wins."),
+        let mut d = card::Deck::new();
+        d.shuffle_with(rng);
+        let mut deal = match draw::DrawDeal::new(players, d) {
+            Ok(deal) => deal,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
         };
-        println!("{}", res);
+        for (i, h) in deal.hands().iter().enumerate() {
+            println!("Player {}: {}", i+1, h);
+        }
+
+        for i in 0..players {
+            let discards = draw::standard_discards( &deal.hands()[i] );
+            let thrown: Vec<String> = discards.iter().map(|p| deal.hands()[i].card(*p).name()).collect();
+            match deal.draw(i, &discards, rng) {
+                Ok(drawn) if drawn.is_empty() => println!("Player {} stands pat.", i+1),
+                Ok(drawn) => {
+                    let names: Vec<String> = drawn.iter().map(|c| c.name()).collect();
+                    println!("Player {} discards {} and draws {}: {}", i+1, thrown.join(" "), drawn.len(), names.join(" "));
+                }
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        for (i, h) in deal.hands().iter().enumerate() {
+            println!("Player {}: {}", i+1, h);
+            println!("  Best hand for Player {}: {}", i+1, deal.hand_data(i));
+        }
+        println!("{}", announce(&deal.winners()));
+    }
+}
+
+fn draw_stats(players: usize, deals: u32, rng: &mut StdRng) {
+    let stats = match draw::simulate(players, deals, rng) {
+        Ok(stats) => stats,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("Hands: {}", stats.hands);
+    println!("Stand pat: {:.2}%", 100.0 * stats.frequency(0));
+    for n in 1..=5 {
+        println!("Draw {}: {:.2}%", n, 100.0 * stats.frequency(n));
+    }
+    println!("Improved: {:.2}%", 100.0 * stats.improvement());
+}
+
+fn show_equity(game: card::Variant, hands: &[card::Hand], board: &card::Hand, dead: &card::Hand,
+               trials: u32, rng: &mut StdRng) {
+    let res = match equity::calculate(game, hands, board, dead, trials, rng) {
+        Ok(res) => res,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if board.count() > 0 {
+        println!("Board: {}", board);
+    }
+    if res.exact {
+        println!("Boards enumerated: {}", res.boards);
+    } else {
+        println!("Boards dealt: {}", res.boards);
+    }
+    for (i, h) in hands.iter().enumerate() {
+        println!("Player {}: {}  win {:.2}%  tie {:.2}%  lose {:.2}%  equity {:.2}% \u{b1} {:.2}%",
+            i+1, h, 100.0 * res.win(i), 100.0 * res.tie(i), 100.0 * res.loss(i),
+            100.0 * res.equity(i), 100.0 * res.std_error(i));
+    }
+}
+
+fn write_preflop_table(opponents: usize, trials: u32, output: Option<&std::path::Path>, rng: &mut StdRng) {
+    let table = preflop::PreflopTable::generate(opponents, trials, rng);
+    match output {
+        Some(path) => {
+            if let Err(e) = table.save(path) {
+                eprintln!("error: cannot write {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{}", table),
+    }
+}
+
+fn play(opponents: usize, stack: u32, big_blind: u32, preflop: Option<preflop::PreflopTable>, rng: &mut StdRng) {
+    let config = game::TableConfig { small_blind: big_blind / 2, big_blind, ante: 0 };
+    let mut table = match game::Table::new(config, &vec![stack; opponents + 1]) {
+        Ok(table) => table,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    // a mix of playing styles, in turn around the table
+    let mut bots: Vec<Box<dyn agent::Agent>> = (0..opponents)
+        .map(|i| -> Box<dyn agent::Agent> {
+            match i % 3 {
+                0 => Box::new(agent::TightAggressive::default()),
+                1 => {
+                    let bot = agent::EquityCaller::new(0.5, 500, rng.gen());
+                    match &preflop {
+                        Some(table) => Box::new(bot.with_preflop_table(table.clone())),
+                        None => Box::new(bot),
+                    }
+                }
+                _ => Box::new(agent::CallingStation),
+            }
+        })
+        .collect();
+    let stdin = std::io::stdin();
+    if let Err(e) = play::play_session(&mut table, &mut bots, &mut stdin.lock(), &mut std::io::stdout(), rng) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
     }
 }
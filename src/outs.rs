@@ -0,0 +1,168 @@
+use crate::card::{Card, Deck, Hand, HandRank};
+use crate::equity::{EquityError, unseen_deck};
+use crate::eval::{evaluate, hand_rank, CardSet};
+use std::fmt;
+
+/// What an out makes. Overcards pair a hole card above the whole board;
+/// anything else counts as the hand it makes.
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
+pub enum OutKind {
+    Overcard,
+    Made(HandRank),
+}
+
+impl OutKind {
+    pub fn name(&self) -> String {
+        match self {
+            OutKind::Overcard => String::from("Overcard"),
+            OutKind::Made(rank) => rank.name(),
+        }
+    }
+}
+
+impl fmt::Display for OutKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// One card that helps the player. A tainted out improves their hand but
+/// may not leave it best, see `find_outs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Out {
+    pub card: Card,
+    pub kind: OutKind,
+    pub clean: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outs {
+    /// The player's hand before the next card.
+    pub current: HandRank,
+    /// Every out, in deck order.
+    pub outs: Vec<Out>,
+}
+
+impl Outs {
+    pub fn count(&self) -> usize {
+        self.outs.len()
+    }
+
+    pub fn clean(&self) -> usize {
+        self.outs.iter().filter(|o| o.clean).count()
+    }
+
+    /// The outs grouped by what they make, best first.
+    pub fn groups(&self) -> Vec<(OutKind, Vec<&Out>)> {
+        let mut kinds: Vec<OutKind> = self.outs.iter().map(|o| o.kind).collect();
+        kinds.sort_by(|a, b| b.cmp(a));
+        kinds.dedup();
+        kinds.into_iter()
+            .map(|k| (k, self.outs.iter().filter(|o| o.kind == k).collect()))
+            .collect()
+    }
+}
+
+// Check the deal and return the cards still unseen. Player 0 is the one
+// whose outs are counted.
+fn unseen(hole: &Hand, board: &Hand, opponents: &[Hand]) -> Result<Vec<Card>, EquityError> {
+    for (i, h) in [hole].into_iter().chain(opponents).enumerate() {
+        if h.count() != 2 {
+            return Err(EquityError::WrongHoleCards { player: i, count: h.count(), expected: 2 });
+        }
+    }
+    if board.count() != 3 && board.count() != 4 {
+        return Err(EquityError::WrongBoard { count: board.count(), min: 3, max: 4 });
+    }
+    let deck = unseen_deck( Deck::new(), [hole, board].into_iter().chain(opponents) )?;
+    Ok(deck.cards().to_vec())
+}
+
+// What the board alone makes, which every player shares. Four cards can
+// only make pairs, trips or quads.
+fn board_rank(board: CardSet) -> HandRank {
+    if board.count() >= 5 {
+        return hand_rank(evaluate(board));
+    }
+    let mut counts = [0; 13];
+    for c in board.cards() {
+        counts[c.rank.index()] += 1;
+    }
+    match (counts.iter().max().unwrap(), counts.iter().filter(|n| **n == 2).count()) {
+        (4, _) => HandRank::FourOfAKind,
+        (3, _) => HandRank::ThreeOfAKind,
+        (2, 2) => HandRank::TwoPair,
+        (2, _) => HandRank::Pair,
+        _ => HandRank::HighCard,
+    }
+}
+
+/// The cards that could come next on a flop or turn `board` and help the
+/// player holding `hole`.
+///
+/// With no opponents given, an out is a card that improves the player's
+/// HandRank to better than the board makes on its own. It is tainted if
+/// it could also give some opponent a hand of a higher HandRank than the
+/// player's that they did not have already, as a flush card that pairs the
+/// board might make a full house.
+///
+/// With opponents, an out is also any card that takes the player from not
+/// winning outright to winning. The opponents' cards are known, so an out
+/// is clean exactly when the player wins after it.
+pub fn find_outs(hole: &Hand, board: &Hand, opponents: &[Hand]) -> Result<Outs, EquityError> {
+    let rest = unseen(hole, board, opponents)?;
+    let board_set = CardSet::from_hand(board);
+    let mine = board_set.union( CardSet::from_hand(hole) );
+    let current = evaluate(mine);
+    let theirs: Vec<CardSet> = opponents.iter().map(|h| board_set.union( CardSet::from_hand(h) )).collect();
+    let wins = |me: u16, opp: &[u16]| opp.iter().all(|v| me > *v);
+    let winning = !opponents.is_empty() && wins(current, &theirs.iter().map(|s| evaluate(*s)).collect::<Vec<u16>>());
+
+    // the hands an unknown opponent could hold, with what they make now
+    let mut unknown: Vec<(CardSet, HandRank)> = Vec::new();
+    if opponents.is_empty() {
+        for (i, a) in rest.iter().enumerate() {
+            for b in &rest[i + 1..] {
+                let set = CardSet::from_cards(&[a.clone(), b.clone()]);
+                unknown.push( (set, hand_rank(evaluate(set.union(board_set)))) );
+            }
+        }
+    }
+
+    let top = board.cards().iter().map(|c| c.rank).max().unwrap();
+    let mut outs = Vec::new();
+    for card in &rest {
+        let mut after = mine;
+        after.insert(card);
+        let mut next = board_set;
+        next.insert(card);
+        let value = evaluate(after);
+        let rank = hand_rank(value);
+        let improved = rank > hand_rank(current) && rank > board_rank(next);
+        if !improved && (opponents.is_empty() || winning) {
+            continue;
+        }
+
+        let clean = if opponents.is_empty() {
+            !unknown.iter().any(|(h, before)| {
+                !h.contains(card) && *before <= rank && hand_rank(evaluate(h.union(next))) > rank
+            })
+        } else {
+            let opp: Vec<u16> = theirs.iter().map(|s| { let mut s = *s; s.insert(card); evaluate(s) }).collect();
+            wins(value, &opp)
+        };
+        if !improved && !clean {
+            continue;
+        }
+
+        let overcard = rank <= HandRank::TwoPair && card.rank > top
+            && hole.cards().iter().any(|c| c.rank == card.rank)
+            && hole.cards()[0].rank != hole.cards()[1].rank;
+        let kind = if overcard { OutKind::Overcard } else { OutKind::Made(rank) };
+        outs.push( Out { card: card.clone(), kind, clean } );
+    }
+    Ok(Outs { current: hand_rank(current), outs })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1 @@
+pub mod outs_tests;
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::equity::EquityError;
+    use crate::outs::*;
+    use crate::card::tests::support::hand;
+
+    fn names(outs: &[&Out]) -> String {
+        outs.iter().map(|o| o.card.to_string()).collect::<Vec<String>>().join(" ")
+    }
+
+    #[test]
+    fn flush_draw_with_overcards() {
+        let outs = find_outs( &hand("Ah Kh"), &hand("Qh 7h 2c"), &[] ).unwrap();
+        assert_eq!( outs.current, HandRank::HighCard );
+        assert_eq!( outs.count(), 15 );
+
+        let groups = outs.groups();
+        assert_eq!( groups.len(), 2 );
+        assert_eq!( groups[0].0, OutKind::Made(HandRank::Flush) );
+        assert_eq!( groups[0].1.len(), 9 );
+        assert_eq!( groups[1].0, OutKind::Overcard );
+        assert_eq!( groups[1].1.len(), 6 );
+
+        // pairing the board could make someone a full house or quads
+        let tainted: Vec<&Out> = groups[0].1.iter().copied().filter(|o| !o.clean).collect();
+        assert_eq!( names(&tainted), hand("2h").to_string() );
+        // an ace or king could give someone two pair
+        assert!( groups[1].1.iter().all(|o| !o.clean) );
+        assert_eq!( outs.clean(), 8 );
+    }
+
+    #[test]
+    fn board_pairs_are_not_outs() {
+        let outs = find_outs( &hand("Ah Kh"), &hand("Qh 7h 2c"), &[] ).unwrap();
+        assert!( outs.outs.iter().all(|o| ![CardRank::Queen, CardRank::Seven, CardRank::Two].contains(&o.card.rank)
+            || o.card.suite == CardSuite::Hearts) );
+
+        // an open ended straight draw on the turn
+        let outs = find_outs( &hand("9c 8d"), &hand("7s 6h 2c Kd"), &[] ).unwrap();
+        let groups = outs.groups();
+        assert_eq!( groups[0].0, OutKind::Made(HandRank::Straight) );
+        assert_eq!( groups[0].1.len(), 8 );
+        assert!( groups[0].1.iter().all(|o| o.clean) );
+        // pairing a hole card is not an overcard below the king
+        assert_eq!( groups[1].0, OutKind::Made(HandRank::Pair) );
+        assert_eq!( groups[1].1.len(), 6 );
+    }
+
+    #[test]
+    fn against_known_hands() {
+        let outs = find_outs( &hand("Ah Kh"), &hand("Qh 7h 2c"), &[hand("Qs Qd")] ).unwrap();
+        assert_eq!( outs.count(), 15 );
+        assert_eq!( outs.clean(), 8 );
+        // the flush loses to the full house when the board pairs
+        let tainted: Vec<&Out> = outs.groups()[0].1.iter().copied().filter(|o| !o.clean).collect();
+        assert_eq!( names(&tainted), hand("2h").to_string() );
+        assert!( outs.groups()[1].1.iter().all(|o| !o.clean) );
+
+        // a card that counterfeits the opponent's two pair wins without
+        // improving the player's hand past the board
+        let outs = find_outs( &hand("Ac 5d"), &hand("Ks Kd 7c 2h"), &[hand("2s 3s")] ).unwrap();
+        let sevens: Vec<&Out> = outs.outs.iter().filter(|o| o.card.rank == CardRank::Seven).collect();
+        assert_eq!( sevens.len(), 3 );
+        assert!( sevens.iter().all(|o| o.clean && o.kind == OutKind::Made(HandRank::TwoPair)) );
+
+        // nothing to find once the player is already ahead and cannot improve
+        let outs = find_outs( &hand("Kc Kh"), &hand("Ks Kd 7c 2h"), &[hand("2s 3s")] ).unwrap();
+        assert_eq!( outs.count(), 0 );
+    }
+
+    #[test]
+    fn bad_deals() {
+        assert_eq!( find_outs( &hand("Ah"), &hand("Qh 7h 2c"), &[] ),
+            Err(EquityError::WrongHoleCards { player: 0, count: 1, expected: 2 }) );
+        assert_eq!( find_outs( &hand("Ah Kh"), &hand("Qh 7h 2c"), &[hand("Qs")] ),
+            Err(EquityError::WrongHoleCards { player: 1, count: 1, expected: 2 }) );
+        assert_eq!( find_outs( &hand("Ah Kh"), &hand("Qh 7h"), &[] ),
+            Err(EquityError::WrongBoard { count: 2, min: 3, max: 4 }) );
+        assert_eq!( find_outs( &hand("Ah Kh"), &hand("Qh 7h 2c 3c 4c"), &[] ),
+            Err(EquityError::WrongBoard { count: 5, min: 3, max: 4 }) );
+        assert_eq!( find_outs( &hand("Ah Kh"), &hand("Qh 7h 2c"), &[hand("Ah Qs")] ),
+            Err(EquityError::DuplicateCard(hand("Ah").cards()[0].clone())) );
+        assert_eq!( find_outs( &hand("Ah Xh"), &hand("Qh 7h 2c"), &[] ),
+            Err(EquityError::NotInDeck(hand("Xh").cards()[0].clone())) );
+    }
+}
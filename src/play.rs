@@ -0,0 +1,192 @@
+use crate::agent::Agent;
+use crate::card::Deck;
+use crate::game::{Action, LegalActions, Table};
+use crate::pot::Distribution;
+use rand::Rng;
+use std::io::{self, BufRead, Write};
+
+/// The human player always sits in the first seat; bots fill the rest.
+pub const HUMAN: usize = 0;
+
+/// Read an action from a line of input such as "call", "r 60" or "allin".
+/// Whether it is legal is left to the table.
+pub fn parse_action(line: &str, legal: &LegalActions) -> Result<Action, String> {
+    let words: Vec<String> = line.split_whitespace().map(|w| w.to_lowercase()).collect();
+    let amount = || match words.get(1) {
+        Some(w) => w.parse::<u32>().map_err(|_| format!("'{}' is not an amount", w)),
+        None => Err(String::from("say how much, e.g. \"r 60\"")),
+    };
+    match words.first().map(|w| w.as_str()) {
+        Some("f") | Some("fold") => Ok(Action::Fold),
+        Some("k") | Some("check") => Ok(Action::Check),
+        // "call" when there is nothing to call is a check
+        Some("c") | Some("call") => Ok(if legal.check { Action::Check } else { Action::Call }),
+        Some("b") | Some("bet") => Ok(Action::Bet(amount()?)),
+        Some("r") | Some("raise") => Ok(Action::Raise(amount()?)),
+        Some("a") | Some("allin") => Ok(match (legal.bet, legal.raise) {
+            (Some((_, max)), _) => Action::Bet(max),
+            (_, Some((_, max))) => Action::Raise(max),
+            _ => Action::Call,
+        }),
+        Some(w) => Err(format!("unknown action '{}'", w)),
+        None => Err(String::from("enter an action")),
+    }
+}
+
+/// List the legal actions the way they can be typed in.
+pub fn describe(legal: &LegalActions) -> String {
+    let mut options = vec![String::from("[f]old")];
+    if legal.check {
+        options.push(String::from("chec[k]"));
+    }
+    if let Some(n) = legal.call {
+        options.push(format!("[c]all {}", n));
+    }
+    if let Some((min, max)) = legal.bet {
+        options.push(format!("[b]et {}-{}", min, max));
+    }
+    if let Some((min, max)) = legal.raise {
+        options.push(format!("[r]aise to {}-{}", min, max));
+    }
+    if legal.bet.is_some() || legal.raise.is_some() {
+        options.push(String::from("[a]llin"));
+    }
+    options.join(", ")
+}
+
+fn seat_name(seat: usize, bots: &[Box<dyn Agent>]) -> String {
+    if seat == HUMAN {
+        String::from("You")
+    } else {
+        format!("Player {} ({})", seat + 1, bots[seat - 1].name())
+    }
+}
+
+fn street_name(board: usize) -> &'static str {
+    match board {
+        3 => "Flop",
+        4 => "Turn",
+        _ => "River",
+    }
+}
+
+// Ask until the human enters an action; None when they quit or input ends.
+fn read_action<B: BufRead, W: Write>(legal: &LegalActions, to_call: u32, pot: u32,
+                                     input: &mut B, out: &mut W) -> io::Result<Option<Action>> {
+    loop {
+        write!(out, "Pot {}, to call {}. {}? ", pot, to_call, describe(legal))?;
+        out.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim() == "q" || line.trim() == "quit" {
+            writeln!(out)?;
+            return Ok(None);
+        }
+        match parse_action(&line, legal) {
+            Ok(action) => return Ok(Some(action)),
+            Err(e) => writeln!(out, "{}", e)?,
+        }
+    }
+}
+
+/// Deal and play out one hand, asking the human for their actions and the
+/// bots for theirs. Returns false if the human asked to stop, in which case
+/// they check or fold for the rest of the hand.
+pub fn play_hand<B: BufRead, W: Write, G: Rng + ?Sized>(table: &mut Table, bots: &mut [Box<dyn Agent>],
+                                                        input: &mut B, out: &mut W,
+                                                        rng: &mut G) -> io::Result<bool> {
+    let mut deck = Deck::new();
+    deck.shuffle_with(rng);
+    table.start_hand(deck).map_err(io::Error::other)?;
+
+    writeln!(out, "--- Hand {} ---", table.hands_played())?;
+    writeln!(out, "Button: {}", seat_name(table.button(), bots))?;
+    if table.seats()[HUMAN].dealt_in {
+        writeln!(out, "Your cards: {}", table.seats()[HUMAN].hole)?;
+    }
+
+    let mut playing = true;
+    let mut board = 0;
+    while let Some(view) = table.view() {
+        let action = if view.seat == HUMAN {
+            let chosen = if playing {
+                read_action(&view.legal, view.to_call(), view.pot, input, out)?
+            } else {
+                None
+            };
+            match chosen {
+                Some(action) => action,
+                None => {
+                    playing = false;
+                    if view.legal.check { Action::Check } else { Action::Fold }
+                }
+            }
+        } else {
+            bots[view.seat - 1].act(&view)
+        };
+        match table.act( view.seat, action ) {
+            Ok(()) => writeln!(out, "{}: {}", seat_name(view.seat, bots), action)?,
+            Err(e) if view.seat == HUMAN => writeln!(out, "{}", e)?,
+            Err(_) => {
+                let fallback = if view.legal.check { Action::Check } else { Action::Fold };
+                table.act( view.seat, fallback ).unwrap();
+                writeln!(out, "{}: {}", seat_name(view.seat, bots), fallback)?;
+            }
+        }
+        if table.board().count() > board {
+            board = table.board().count();
+            writeln!(out, "{}: {}", street_name(board), table.board())?;
+        }
+    }
+    show_result(table, bots, out)?;
+    Ok(playing)
+}
+
+// Showdown hands, where the chips went and everyone's stack.
+fn show_result<W: Write>(table: &Table, bots: &[Box<dyn Agent>], out: &mut W) -> io::Result<()> {
+    let res = table.result().expect("hand is over");
+    let showdown = res.shown.iter().filter(|s| s.is_some()).count() > 1;
+    if showdown {
+        for (i, best) in res.shown.iter().enumerate() {
+            if let Some(best) = best {
+                writeln!(out, "{} {} {}:{}", seat_name(i, bots), if i == HUMAN { "show" } else { "shows" },
+                    table.seats()[i].hole, best.data)?;
+            }
+        }
+        let dist = Distribution { payouts: res.payouts.clone(), awards: res.pots.clone(), returned: res.returned };
+        write!(out, "{}", dist)?;
+    } else {
+        // the pot, less any of the winner's own bet that nobody called
+        let returned = |i: usize| res.returned.filter(|(r, _)| *r == i).map_or(0, |(_, amount)| amount);
+        for (i, p) in res.payouts.iter().enumerate().filter(|(i, p)| **p > returned(*i)) {
+            writeln!(out, "{} {} {}.", seat_name(i, bots), if i == HUMAN { "win" } else { "wins" }, p - returned(i))?;
+        }
+        if let Some((i, amount)) = res.returned {
+            writeln!(out, "Uncalled {} returned to {}.", amount, seat_name(i, bots))?;
+        }
+    }
+    let stacks: Vec<String> = table.seats().iter().enumerate()
+        .map(|(i, s)| format!("{} {}", if i == HUMAN { String::from("You") } else { format!("Player {}", i + 1) }, s.stack))
+        .collect();
+    writeln!(out, "Stacks: {}", stacks.join(", "))
+}
+
+/// Keep dealing until the human quits, runs out of chips or has won
+/// everyone else's.
+pub fn play_session<B: BufRead, W: Write, G: Rng + ?Sized>(table: &mut Table, bots: &mut [Box<dyn Agent>],
+                                                           input: &mut B, out: &mut W,
+                                                           rng: &mut G) -> io::Result<()> {
+    loop {
+        if table.seats()[HUMAN].stack == 0 {
+            return writeln!(out, "You are out of chips.");
+        }
+        if table.seats().iter().filter(|s| s.stack > 0).count() < 2 {
+            return writeln!(out, "You have won every chip.");
+        }
+        if !play_hand(table, bots, input, out, rng)? {
+            return writeln!(out, "Thanks for playing.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use crate::agent::*;
+    use crate::game::*;
+    use crate::play::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::io::Cursor;
+
+    const CONFIG: TableConfig = TableConfig { small_blind: 5, big_blind: 10, ante: 0 };
+
+    fn legal(check: bool) -> LegalActions {
+        if check {
+            LegalActions { check: true, call: None, bet: Some((10, 500)), raise: None }
+        } else {
+            LegalActions { check: false, call: Some(10), bet: None, raise: Some((20, 500)) }
+        }
+    }
+
+    #[test]
+    fn parse_actions() {
+        assert_eq!( parse_action("f", &legal(false)), Ok(Action::Fold) );
+        assert_eq!( parse_action(" Call ", &legal(false)), Ok(Action::Call) );
+        assert_eq!( parse_action("c", &legal(true)), Ok(Action::Check) );
+        assert_eq!( parse_action("b 40", &legal(true)), Ok(Action::Bet(40)) );
+        assert_eq!( parse_action("raise 60", &legal(false)), Ok(Action::Raise(60)) );
+        assert_eq!( parse_action("a", &legal(true)), Ok(Action::Bet(500)) );
+        assert_eq!( parse_action("allin", &legal(false)), Ok(Action::Raise(500)) );
+        assert!( parse_action("r", &legal(false)).is_err() );
+        assert!( parse_action("r lots", &legal(false)).is_err() );
+        assert!( parse_action("shove", &legal(false)).is_err() );
+        assert!( parse_action("", &legal(false)).is_err() );
+    }
+
+    #[test]
+    fn describe_actions() {
+        assert_eq!( describe(&legal(true)), "[f]old, chec[k], [b]et 10-500, [a]llin" );
+        assert_eq!( describe(&legal(false)), "[f]old, [c]all 10, [r]aise to 20-500, [a]llin" );
+    }
+
+    #[test]
+    fn scripted_session() {
+        let mut table = Table::new( CONFIG, &[1000, 1000] ).unwrap();
+        let mut bots: Vec<Box<dyn Agent>> = vec![Box::new(CallingStation)];
+        let mut rng = StdRng::seed_from_u64(5);
+        // a typo, then calling and checking down the first hand
+        let mut input = Cursor::new("x\nc\nc\nc\nc\nq\n");
+        let mut out = Vec::new();
+        play_session( &mut table, &mut bots, &mut input, &mut out, &mut rng ).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!( out.contains("unknown action 'x'") );
+        assert!( out.contains("Your cards: ") );
+        assert!( out.contains("River: ") );
+        assert!( out.contains("You show ") );
+        assert!( out.contains("Player 2 (Calling Station) shows ") );
+        assert!( out.contains("Main pot 20") );
+        assert!( out.ends_with("Thanks for playing.\n") );
+        assert_eq!( table.hands_played(), 2 );
+        assert_eq!( table.seats().iter().map(|s| s.stack).sum::<u32>(), 2000 );
+    }
+
+    #[test]
+    fn fold_returns_the_uncalled_bet() {
+        let mut table = Table::new( CONFIG, &[1000, 1000] ).unwrap();
+        let mut bots: Vec<Box<dyn Agent>> = vec![Box::new(CallingStation)];
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut out = Vec::new();
+        play_session( &mut table, &mut bots, &mut Cursor::new("f\nq\n"), &mut out, &mut rng ).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        // the small blind folds: the big blind wins the pot of 10 and gets
+        // back the other 5 it put in
+        assert!( out.contains("You: fold\nPlayer 2 (Calling Station) wins 10.\nUncalled 5 returned to Player 2 (Calling Station).\n") );
+    }
+
+    #[test]
+    fn session_ends_when_input_does() {
+        let mut table = Table::new( CONFIG, &[1000, 1000, 1000] ).unwrap();
+        let mut bots: Vec<Box<dyn Agent>> = vec![Box::new(CallingStation), Box::new(CallingStation)];
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut out = Vec::new();
+        play_session( &mut table, &mut bots, &mut Cursor::new(""), &mut out, &mut rng ).unwrap();
+        assert_eq!( table.hands_played(), 1 );
+        assert!( !table.in_progress() );
+        assert!( String::from_utf8(out).unwrap().ends_with("Thanks for playing.\n") );
+    }
+
+    #[test]
+    fn busted_human_stops_session() {
+        let mut table = Table::new( CONFIG, &[0, 1000, 1000] ).unwrap();
+        let mut bots: Vec<Box<dyn Agent>> = vec![Box::new(CallingStation), Box::new(CallingStation)];
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut out = Vec::new();
+        play_session( &mut table, &mut bots, &mut Cursor::new(""), &mut out, &mut rng ).unwrap();
+        assert_eq!( String::from_utf8(out).unwrap(), "You are out of chips.\n" );
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+
+/// A main or side pot and the players who can win it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: u32,
+    pub eligible: Vec<usize>,
+}
+
+/// One line of the audit trail: who won a pot and what each was paid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PotAward {
+    pub pot: Pot,
+    pub winners: Vec<usize>,
+    pub share: u32,
+    // winners given one extra chip when the pot did not split evenly
+    pub odd_chips: Vec<usize>,
+}
+
+/// How the chips were paid out, per player, with the awards behind it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    pub payouts: Vec<u32>,
+    pub awards: Vec<PotAward>,
+    // the uncalled part of a bet, given back to the player who made it
+    pub returned: Option<(usize, u32)>,
+}
+
+fn seat_list(seats: &[usize]) -> String {
+    let names: Vec<String> = seats.iter().map(|s| (s + 1).to_string()).collect();
+    names.join(", ")
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, a) in self.awards.iter().enumerate() {
+            let name = if i == 0 { String::from("Main pot") } else { format!("Side pot {}", i) };
+            write!(f, "{} {} (players {}): ", name, a.pot.amount, seat_list(&a.pot.eligible))?;
+            if a.winners.len() == 1 {
+                write!(f, "won by player {}", a.winners[0] + 1)?;
+            } else {
+                write!(f, "split {} each by players {}", a.share, seat_list(&a.winners))?;
+            }
+            if !a.odd_chips.is_empty() {
+                write!(f, ", odd chip to player {}", seat_list(&a.odd_chips))?;
+            }
+            writeln!(f)?;
+        }
+        write_returned(f, self.returned)
+    }
+}
+
+fn write_returned(f: &mut fmt::Formatter, returned: Option<(usize, u32)>) -> fmt::Result {
+    match returned {
+        Some((player, amount)) => writeln!(f, "Uncalled {}: returned to player {}", amount, player + 1),
+        None => Ok(()),
+    }
+}
+
+/// The part of a bet nobody else matched: the player who put in the most,
+/// and how much more than anyone else, folded or not.
+pub fn uncalled(contributions: &[u32]) -> Option<(usize, u32)> {
+    let top = (0..contributions.len()).max_by_key(|i| contributions[*i])?;
+    let next = (0..contributions.len()).filter(|i| *i != top).map(|i| contributions[i]).max().unwrap_or(0);
+    if contributions[top] > next {
+        Some((top, contributions[top] - next))
+    } else {
+        None
+    }
+}
+
+// What each player put in that someone else matched.
+fn called(contributions: &[u32]) -> Vec<u32> {
+    let mut called = contributions.to_vec();
+    if let Some((player, amount)) = uncalled(contributions) {
+        called[player] -= amount;
+    }
+    called
+}
+
+/// Split what each player put in into a main pot and side pots. A new pot
+/// starts at each all-in level of a player who has not folded; chips from
+/// folded players stay in the pots they reached. The `uncalled` part of a
+/// bet is in no pot.
+pub fn build_pots(contributions: &[u32], folded: &[bool]) -> Vec<Pot> {
+    let contributions = &called(contributions);
+    let n = contributions.len();
+    let mut levels: Vec<u32> = (0..n)
+        .filter(|i| !folded[*i] && contributions[*i] > 0)
+        .map(|i| contributions[i])
+        .collect();
+    levels.sort();
+    levels.dedup();
+
+    let mut pots = Vec::new();
+    let mut prev = 0;
+    for (l, level) in levels.iter().enumerate() {
+        // dead money above the top level goes in with the last pot
+        let top = if l + 1 == levels.len() { u32::MAX } else { *level };
+        let amount = contributions.iter().map(|c| (*c).min(top) - (*c).min(prev)).sum();
+        let eligible = (0..n).filter(|i| !folded[*i] && contributions[*i] >= *level).collect();
+        pots.push( Pot { amount, eligible } );
+        prev = *level;
+    }
+    pots
+}
+
+// Award a pot to the best of its eligible players, adding to `payouts`.
+fn award<T: Ord>(pot: Pot, hands: &[Option<T>], odd_chip_order: &[usize], payouts: &mut [u32]) -> PotAward {
+    let best = pot.eligible.iter().map(|i| &hands[*i]).max().unwrap();
+    let mut winners: Vec<usize> = pot.eligible.iter()
+        .copied()
+        .filter(|i| hands[*i] == *best)
+        .collect();
+    winners.sort_by_key(|w| odd_chip_order.iter().position(|o| o == w));
+    let share = pot.amount / winners.len() as u32;
+    let odd = (pot.amount % winners.len() as u32) as usize;
+    for w in &winners {
+        payouts[*w] += share;
+    }
+    let odd_chips: Vec<usize> = winners[..odd].to_vec();
+    for w in &odd_chips {
+        payouts[*w] += 1;
+    }
+    PotAward { pot, winners, share, odd_chips }
+}
+
+/// Pay out every pot to the best of its eligible players. `hands` ranks the
+/// players, better hands comparing greater; players without a hand to show
+/// lose to any that have one. Odd chips from a split go to the winners in
+/// the order of `odd_chip_order`, usually clockwise from the button. Any
+/// uncalled part of a bet goes back to the player who made it.
+pub fn distribute<T: Ord>(contributions: &[u32], folded: &[bool], hands: &[Option<T>],
+                          odd_chip_order: &[usize]) -> Distribution {
+    let mut payouts = vec![0; contributions.len()];
+    let awards = build_pots(contributions, folded).into_iter()
+        .map(|pot| award(pot, hands, odd_chip_order, &mut payouts))
+        .collect();
+    let returned = uncalled(contributions);
+    if let Some((player, amount)) = returned {
+        payouts[player] += amount;
+    }
+    Distribution { payouts, awards, returned }
+}
+
+/// A pot in a split game: half to the best high hand and half to the best
+/// qualifying low, or all of it to the high hand when nobody has a low.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HiLoAward {
+    pub pot: Pot,
+    pub high: PotAward,
+    pub low: Option<PotAward>,
+}
+
+impl HiLoAward {
+    /// The player who won the whole pot, if one did.
+    pub fn scooped_by(&self) -> Option<usize> {
+        if self.high.winners.len() != 1 {
+            return None;
+        }
+        let winner = self.high.winners[0];
+        match &self.low {
+            Some(low) if low.winners != [winner] => None,
+            _ => Some(winner),
+        }
+    }
+
+    /// Players who only won a quarter of the pot, by tying for one half
+    /// with one other player and taking nothing of the other half.
+    pub fn quartered(&self) -> Vec<usize> {
+        let low = match &self.low {
+            Some(low) => low,
+            None => return Vec::new(),
+        };
+        let mut res = Vec::new();
+        for (half, other) in [(&self.high, low), (low, &self.high)] {
+            if half.winners.len() == 2 {
+                res.extend( half.winners.iter().filter(|w| !other.winners.contains(w)) );
+            }
+        }
+        res.sort();
+        res
+    }
+}
+
+/// How the chips were paid out in a split game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HiLoDistribution {
+    pub payouts: Vec<u32>,
+    pub awards: Vec<HiLoAward>,
+    pub returned: Option<(usize, u32)>,
+}
+
+// "player 1", or "players 1, 2 (25 each)" for a split half.
+fn half_winners(a: &PotAward) -> String {
+    if a.winners.len() == 1 {
+        format!("player {}", a.winners[0] + 1)
+    } else {
+        format!("players {} ({} each)", seat_list(&a.winners), a.share)
+    }
+}
+
+impl fmt::Display for HiLoDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, a) in self.awards.iter().enumerate() {
+            let name = if i == 0 { String::from("Main pot") } else { format!("Side pot {}", i) };
+            write!(f, "{} {} (players {}): ", name, a.pot.amount, seat_list(&a.pot.eligible))?;
+            match (a.scooped_by(), &a.low) {
+                // nobody to split with, so there is no low to speak of
+                (Some(w), None) if a.pot.eligible.len() == 1 => write!(f, "won by player {}", w + 1)?,
+                (Some(w), None) => write!(f, "scooped by player {}, no qualifying low", w + 1)?,
+                (Some(w), Some(_)) => write!(f, "scooped by player {}", w + 1)?,
+                (None, None) => write!(f, "high to {}, no qualifying low", half_winners(&a.high))?,
+                (None, Some(low)) => {
+                    write!(f, "high {} to {}, low {} to {}", a.high.pot.amount, half_winners(&a.high),
+                           low.pot.amount, half_winners(low))?;
+                    let quartered = a.quartered();
+                    if !quartered.is_empty() {
+                        write!(f, "; quartered: player {}", seat_list(&quartered))?;
+                    }
+                }
+            }
+            let odd: Vec<usize> = a.high.odd_chips.iter()
+                .chain(a.low.iter().flat_map(|l| l.odd_chips.iter()))
+                .copied()
+                .collect();
+            if !odd.is_empty() {
+                write!(f, ", odd chip to player {}", seat_list(&odd))?;
+            }
+            writeln!(f)?;
+        }
+        write_returned(f, self.returned)
+    }
+}
+
+/// Pay out every pot in a split game. Each pot with a qualifying low among
+/// its players is halved, the odd chip going to the high half; `highs` and
+/// `lows` rank the players as in `distribute`, with None for no low.
+pub fn distribute_hi_lo<H: Ord, L: Ord>(contributions: &[u32], folded: &[bool], highs: &[Option<H>],
+                                        lows: &[Option<L>], odd_chip_order: &[usize]) -> HiLoDistribution {
+    let mut payouts = vec![0; contributions.len()];
+    let mut awards = Vec::new();
+    for pot in build_pots(contributions, folded) {
+        let has_low = pot.eligible.len() > 1 && pot.eligible.iter().any(|i| lows[*i].is_some());
+        if !has_low {
+            let high = award(pot.clone(), highs, odd_chip_order, &mut payouts);
+            awards.push( HiLoAward { pot, high, low: None } );
+            continue;
+        }
+        let low_half = Pot { amount: pot.amount / 2, eligible: pot.eligible.clone() };
+        let high_half = Pot { amount: pot.amount - low_half.amount, eligible: pot.eligible.clone() };
+        let high = award(high_half, highs, odd_chip_order, &mut payouts);
+        let low = award(low_half, lows, odd_chip_order, &mut payouts);
+        awards.push( HiLoAward { pot, high, low: Some(low) } );
+    }
+    let returned = uncalled(contributions);
+    if let Some((player, amount)) = returned {
+        payouts[player] += amount;
+    }
+    HiLoDistribution { payouts, awards, returned }
+}
+
+#[cfg(test)]
+mod tests;
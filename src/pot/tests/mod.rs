@@ -0,0 +1 @@
+pub mod pot_tests;
@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod tests {
+    use crate::pot::*;
+
+    const ORDER: [usize; 4] = [0, 1, 2, 3];
+
+    fn paid_out(d: &Distribution) -> u32 {
+        d.payouts.iter().sum()
+    }
+
+    #[test]
+    fn single_pot() {
+        let pots = build_pots( &[100, 100, 100], &[false, false, false] );
+        assert_eq!( pots, vec![Pot { amount: 300, eligible: vec![0, 1, 2] }] );
+        let d = distribute( &[100, 100, 100], &[false, false, false], &[Some(1), Some(3), Some(2)], &ORDER );
+        assert_eq!( d.payouts, vec![0, 300, 0] );
+        assert_eq!( d.awards.len(), 1 );
+        assert_eq!( d.awards[0].winners, vec![1] );
+        assert_eq!( d.returned, None );
+    }
+
+    #[test]
+    fn multiple_all_ins() {
+        // the shortest stack has the best hand, the biggest stacks the worst
+        let contrib = [100, 250, 500, 500];
+        let folded = [false; 4];
+        let pots = build_pots( &contrib, &folded );
+        assert_eq!( pots, vec![
+            Pot { amount: 400, eligible: vec![0, 1, 2, 3] },
+            Pot { amount: 450, eligible: vec![1, 2, 3] },
+            Pot { amount: 500, eligible: vec![2, 3] },
+        ] );
+        let d = distribute( &contrib, &folded, &[Some(4), Some(3), Some(2), Some(1)], &ORDER );
+        assert_eq!( d.payouts, vec![400, 450, 500, 0] );
+        assert_eq!( paid_out(&d), 1350 );
+
+        // the biggest stack wins everything
+        let d = distribute( &contrib, &folded, &[Some(1), Some(2), Some(3), Some(4)], &ORDER );
+        assert_eq!( d.payouts, vec![0, 0, 0, 1350] );
+        assert_eq!( d.awards.len(), 3 );
+    }
+
+    #[test]
+    fn folded_chips_are_dead_money() {
+        let contrib = [50, 100, 200];
+        let folded = [true, false, false];
+        let pots = build_pots( &contrib, &folded );
+        // nobody matched the last 100, so it is in no pot
+        assert_eq!( pots, vec![Pot { amount: 250, eligible: vec![1, 2] }] );
+        // even the best hand gets nothing once folded
+        let d = distribute( &contrib, &folded, &[Some(9), Some(2), Some(1)], &ORDER );
+        assert_eq!( d.payouts, vec![0, 250, 100] );
+        assert_eq!( d.returned, Some((2, 100)) );
+
+        // a player who folded after putting in more than an all-in player
+        let contrib = [300, 100, 300];
+        let folded = [true, false, false];
+        let pots = build_pots( &contrib, &folded );
+        assert_eq!( pots, vec![
+            Pot { amount: 300, eligible: vec![1, 2] },
+            Pot { amount: 400, eligible: vec![2] },
+        ] );
+        let d = distribute( &contrib, &folded, &[None, Some(5), Some(1)], &ORDER );
+        assert_eq!( d.payouts, vec![0, 300, 400] );
+        assert_eq!( d.returned, None );
+    }
+
+    #[test]
+    fn split_pots_and_odd_chips() {
+        // three way tie on 100 chips; order starts after the button
+        let contrib = [30, 30, 30, 10];
+        let folded = [false, false, false, true];
+        let d = distribute( &contrib, &folded, &[Some(1), Some(1), Some(1), None], &[1, 2, 3, 0] );
+        assert_eq!( d.payouts, vec![33, 34, 33, 0] );
+        assert_eq!( d.awards[0].share, 33 );
+        assert_eq!( d.awards[0].odd_chips, vec![1] );
+
+        // tie for the main pot, side pot won outright
+        let contrib = [51, 100, 100, 0];
+        let folded = [false, false, false, true];
+        let d = distribute( &contrib, &folded, &[Some(2), Some(2), Some(1), None], &[2, 3, 0, 1] );
+        // main pot 153 split between seats 0 and 1, seat 0 is earlier in the order
+        assert_eq!( d.awards[0].pot.amount, 153 );
+        assert_eq!( d.awards[0].odd_chips, vec![0] );
+        assert_eq!( d.awards[1].pot.amount, 98 );
+        assert_eq!( d.payouts, vec![77, 76 + 98, 0, 0] );
+        assert_eq!( paid_out(&d), 251 );
+    }
+
+    #[test]
+    fn uncontested() {
+        // everybody else folded: no hands are shown
+        let contrib = [10, 5, 40];
+        let folded = [true, true, false];
+        let d = distribute::<u32>( &contrib, &folded, &[None, None, None], &ORDER );
+        assert_eq!( d.payouts, vec![0, 0, 55] );
+        assert_eq!( d.awards.len(), 1 );
+        assert_eq!( d.returned, Some((2, 30)) );
+    }
+
+    #[test]
+    fn folds_to_the_big_blind() {
+        // the big blind wins the small blind and gets back the rest
+        let pots = build_pots( &[5, 10], &[true, false] );
+        assert_eq!( pots, vec![Pot { amount: 10, eligible: vec![1] }] );
+        let d = distribute::<u32>( &[5, 10], &[true, false], &[None, None], &ORDER );
+        assert_eq!( d.payouts, vec![0, 15] );
+        assert_eq!( d.to_string(),
+            "Main pot 10 (players 2): won by player 2\n\
+             Uncalled 5: returned to player 2\n" );
+        let d = distribute_hi_lo::<u32, u32>( &[5, 10], &[true, false], &[None, None], &[None, None], &ORDER );
+        assert_eq!( d.payouts, vec![0, 15] );
+        assert_eq!( d.to_string(),
+            "Main pot 10 (players 2): won by player 2\n\
+             Uncalled 5: returned to player 2\n" );
+
+        // dead money from a fold above an all-in is won, not returned
+        let d = distribute( &[50, 30, 100], &[true, false, false], &[None, Some(2), Some(1)], &ORDER );
+        assert_eq!( d.payouts, vec![0, 90, 90] );
+        assert_eq!( d.to_string(),
+            "Main pot 90 (players 2, 3): won by player 2\n\
+             Side pot 1 40 (players 3): won by player 3\n\
+             Uncalled 50: returned to player 3\n" );
+    }
+
+    #[test]
+    fn audit_trail() {
+        let contrib = [100, 250, 500, 500];
+        let d = distribute( &contrib, &[false; 4], &[Some(4), Some(3), Some(1), Some(1)], &ORDER );
+        assert_eq!( d.to_string(),
+            "Main pot 400 (players 1, 2, 3, 4): won by player 1\n\
+             Side pot 1 450 (players 2, 3, 4): won by player 2\n\
+             Side pot 2 500 (players 3, 4): split 250 each by players 3, 4\n" );
+
+        let d = distribute( &[50, 100], &[false, false], &[Some(1), Some(1)], &ORDER );
+        assert_eq!( d.to_string(),
+            "Main pot 100 (players 1, 2): split 50 each by players 1, 2\n\
+             Uncalled 50: returned to player 2\n" );
+
+        let d = distribute( &[3, 2], &[false, false], &[Some(1), Some(1)], &[1, 0] );
+        assert_eq!( d.to_string(),
+            "Main pot 4 (players 1, 2): split 2 each by players 2, 1\n\
+             Uncalled 1: returned to player 1\n" );
+    }
+
+    const NO_LOW: Option<u32> = None;
+
+    #[test]
+    fn hi_lo_scoop_and_no_low() {
+        let contrib = [100, 100, 100];
+        let folded = [false; 3];
+        // one player has both the best high and the best low
+        let d = distribute_hi_lo( &contrib, &folded, &[Some(3), Some(2), Some(1)], &[Some(5), Some(4), NO_LOW], &ORDER );
+        assert_eq!( d.payouts, vec![300, 0, 0] );
+        assert_eq!( d.awards[0].scooped_by(), Some(0) );
+        assert_eq!( d.to_string(), "Main pot 300 (players 1, 2, 3): scooped by player 1\n" );
+
+        // nobody has a low, so the high hand takes it all
+        let d = distribute_hi_lo( &contrib, &folded, &[Some(1), Some(3), Some(2)], &[NO_LOW; 3], &ORDER );
+        assert_eq!( d.payouts, vec![0, 300, 0] );
+        assert!( d.awards[0].low.is_none() );
+        assert_eq!( d.awards[0].scooped_by(), Some(1) );
+        assert_eq!( d.to_string(), "Main pot 300 (players 1, 2, 3): scooped by player 2, no qualifying low\n" );
+    }
+
+    #[test]
+    fn hi_lo_split_and_quartered() {
+        let contrib = [100, 100, 100];
+        let folded = [false; 3];
+        let d = distribute_hi_lo( &contrib, &folded, &[Some(3), Some(1), Some(1)], &[NO_LOW, Some(2), Some(1)], &ORDER );
+        assert_eq!( d.payouts, vec![150, 150, 0] );
+        assert_eq!( d.awards[0].scooped_by(), None );
+        assert!( d.awards[0].quartered().is_empty() );
+        assert_eq!( d.to_string(), "Main pot 300 (players 1, 2, 3): high 150 to player 1, low 150 to player 2\n" );
+
+        // two players tie for low and get a quarter each
+        let d = distribute_hi_lo( &contrib, &folded, &[Some(3), Some(1), Some(1)], &[NO_LOW, Some(2), Some(2)], &ORDER );
+        assert_eq!( d.payouts, vec![150, 75, 75] );
+        assert_eq!( d.awards[0].quartered(), vec![1, 2] );
+        assert_eq!( d.to_string(),
+            "Main pot 300 (players 1, 2, 3): high 150 to player 1, low 150 to players 2, 3 (75 each); quartered: player 2, 3\n" );
+
+        // a player who ties for low but also wins high is not quartered
+        let d = distribute_hi_lo( &contrib, &folded, &[Some(3), Some(1), Some(1)], &[Some(2), Some(2), NO_LOW], &ORDER );
+        assert_eq!( d.payouts, vec![225, 75, 0] );
+        assert_eq!( d.awards[0].quartered(), vec![1] );
+    }
+
+    #[test]
+    fn hi_lo_odd_chips_and_side_pots() {
+        // 301 chips: the odd chip goes to the high half
+        let d = distribute_hi_lo( &[101, 101, 99], &[false, false, true], &[Some(2), Some(1), None], &[NO_LOW, Some(1), NO_LOW], &ORDER );
+        assert_eq!( d.payouts, vec![151, 150, 0] );
+        assert_eq!( d.payouts.iter().sum::<u32>(), 301 );
+
+        // the low is only split in the pot its owner is in
+        let contrib = [50, 200, 200];
+        let folded = [false; 3];
+        let d = distribute_hi_lo( &contrib, &folded, &[Some(1), Some(3), Some(2)], &[Some(1), NO_LOW, NO_LOW], &ORDER );
+        assert_eq!( d.awards.len(), 2 );
+        assert!( d.awards[0].low.is_some() );
+        assert!( d.awards[1].low.is_none() );
+        assert_eq!( d.payouts, vec![75, 375, 0] );
+        assert_eq!( d.payouts.iter().sum::<u32>(), 450 );
+    }
+}
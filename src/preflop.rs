@@ -0,0 +1,143 @@
+use crate::card::Hand;
+use crate::equity::simulate_vs_random;
+use crate::range::HandClass;
+use rand::Rng;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Error returned when reading a preflop table. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTableError {
+    BadHeader(String),
+    BadLine { line: usize, text: String },
+    MissingHand(HandClass),
+}
+
+impl fmt::Display for ParseTableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseTableError::BadHeader(text) =>
+                write!(f, "expected a header such as 'hand,vs1,vs2', found '{}'", text),
+            ParseTableError::BadLine { line, text } =>
+                write!(f, "invalid entry '{}' on line {}", text, line),
+            ParseTableError::MissingHand(class) =>
+                write!(f, "no entry for {}", class),
+        }
+    }
+}
+
+impl std::error::Error for ParseTableError {}
+
+/// Precomputed all-in equity of each of the 169 starting hand classes
+/// against one or more opponents holding random hands, so a hand's preflop
+/// strength can be looked up instead of simulated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreflopTable {
+    // by number of opponents less one, then by `HandClass::index`
+    equity: Vec<Vec<f64>>,
+}
+
+impl PreflopTable {
+    /// Simulate `trials` deals for every class against each number of
+    /// opponents from 1 to `max_opponents`.
+    pub fn generate<R: Rng + ?Sized>(max_opponents: usize, trials: u32, rng: &mut R) -> PreflopTable {
+        assert!( max_opponents >= 1 );
+        assert!( trials > 0 );
+        let mut equity = vec![vec![0.0; 169]; max_opponents];
+        for class in HandClass::all() {
+            // all hands in a class are worth the same before the flop
+            let hole = &class.combos()[0];
+            for (i, row) in equity.iter_mut().enumerate() {
+                let res = simulate_vs_random( hole, &Hand::new(), i + 1, trials, rng ).unwrap();
+                row[class.index()] = res.equity(0);
+            }
+        }
+        PreflopTable { equity }
+    }
+
+    /// The most opponents the table has equities for.
+    pub fn max_opponents(&self) -> usize {
+        self.equity.len()
+    }
+
+    /// Equity of the class against `opponents` random hands, or None if
+    /// the table does not go that far.
+    pub fn equity(&self, class: HandClass, opponents: usize) -> Option<f64> {
+        if opponents == 0 {
+            return None;
+        }
+        self.equity.get(opponents - 1).map(|row| row[class.index()])
+    }
+
+    /// Equity of two hole cards against `opponents` random hands, or None
+    /// for a hand with a joker, which no class covers.
+    pub fn hand_equity(&self, hole: &Hand, opponents: usize) -> Option<f64> {
+        self.equity( HandClass::of(hole)?, opponents )
+    }
+
+    /// Read a table written by `save`.
+    pub fn load(path: &Path) -> io::Result<PreflopTable> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+// Written as CSV: a header naming the opponent counts, then one line per
+// class, pairs first, with its equity against each.
+impl fmt::Display for PreflopTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let header: Vec<String> = (1..=self.max_opponents()).map(|n| format!("vs{}", n)).collect();
+        writeln!(f, "hand,{}", header.join(","))?;
+        for class in HandClass::all() {
+            let values: Vec<String> = self.equity.iter().map(|row| format!("{:.4}", row[class.index()])).collect();
+            writeln!(f, "{},{}", class, values.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PreflopTable {
+    type Err = ParseTableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+        let header = lines.next().map(|(_, l)| l.trim()).unwrap_or("");
+        let columns: Vec<&str> = header.split(',').collect();
+        let max_opponents = columns.len() - 1;
+        let numbered = columns[1..].iter().enumerate().all(|(i, c)| *c == format!("vs{}", i + 1));
+        if columns[0] != "hand" || max_opponents == 0 || !numbered {
+            return Err(ParseTableError::BadHeader(header.to_string()));
+        }
+
+        let mut equity = vec![vec![f64::NAN; 169]; max_opponents];
+        for (i, line) in lines {
+            let bad = || ParseTableError::BadLine { line: i + 1, text: line.to_string() };
+            let fields: Vec<&str> = line.trim().split(',').collect();
+            if fields.len() != columns.len() {
+                return Err(bad());
+            }
+            let class: HandClass = fields[0].parse().map_err(|_| bad())?;
+            for (row, field) in equity.iter_mut().zip(&fields[1..]) {
+                row[class.index()] = match field.parse::<f64>() {
+                    Ok(e) if (0.0..=1.0).contains(&e) => e,
+                    _ => return Err(bad()),
+                };
+            }
+        }
+        if let Some(class) = HandClass::all().into_iter().find(|c| equity[0][c.index()].is_nan()) {
+            return Err(ParseTableError::MissingHand(class));
+        }
+        Ok(PreflopTable { equity })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1 @@
+pub mod preflop_tests;
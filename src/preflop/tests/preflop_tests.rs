@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::preflop::*;
+    use crate::range::HandClass;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    // A table giving every class the same equity against one opponent.
+    fn flat(equity: &str) -> String {
+        let lines: String = HandClass::all().iter().map(|c| format!("{},{}\n", c, equity)).collect();
+        format!("hand,vs1\n{}", lines)
+    }
+
+    #[test]
+    fn class_indices() {
+        let mut seen = [false; 169];
+        for c in HandClass::all() {
+            assert!( !seen[c.index()] );
+            seen[c.index()] = true;
+        }
+        assert_eq!( "AKs".parse::<HandClass>(), Ok(HandClass::Suited(CardRank::Ace, CardRank::King)) );
+        assert_eq!( "TT".parse::<HandClass>(), Ok(HandClass::Pair(CardRank::Ten)) );
+        assert!( "AK".parse::<HandClass>().is_err() );
+    }
+
+    #[test]
+    fn generate() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let table = PreflopTable::generate( 2, 300, &mut rng );
+        assert_eq!( table.max_opponents(), 2 );
+        let aces = HandClass::Pair(CardRank::Ace);
+        let trash: HandClass = "72o".parse().unwrap();
+        // aces win about 85% heads up and 73% three way
+        assert!( (table.equity(aces, 1).unwrap() - 0.85).abs() < 0.1 );
+        assert!( table.equity(aces, 2).unwrap() < table.equity(aces, 1).unwrap() );
+        assert!( table.equity(trash, 1).unwrap() < 0.45 );
+        assert_eq!( table.hand_equity( &"As Ad".parse().unwrap(), 2 ), table.equity(aces, 2) );
+        assert_eq!( table.hand_equity( &"Xs Ad".parse().unwrap(), 2 ), None );
+        assert_eq!( table.equity(aces, 0), None );
+        assert_eq!( table.equity(aces, 3), None );
+    }
+
+    #[test]
+    fn csv_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let table = PreflopTable::generate( 2, 50, &mut rng );
+        let text = table.to_string();
+        assert_eq!( text.lines().count(), 170 );
+        assert_eq!( text.lines().next(), Some("hand,vs1,vs2") );
+        assert!( text.lines().nth(1).unwrap().starts_with("AA,") );
+
+        let read: PreflopTable = text.parse().unwrap();
+        for c in HandClass::all() {
+            assert!( (read.equity(c, 2).unwrap() - table.equity(c, 2).unwrap()).abs() < 0.0001 );
+        }
+
+        let path = std::env::temp_dir().join(format!("holdem-preflop-{}.csv", std::process::id()));
+        read.save(&path).unwrap();
+        let loaded = PreflopTable::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!( loaded, read );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!( "hand,vs2\nAA,0.5".parse::<PreflopTable>().err(),
+            Some(ParseTableError::BadHeader(String::from("hand,vs2"))) );
+        assert_eq!( "".parse::<PreflopTable>().err(), Some(ParseTableError::BadHeader(String::new())) );
+        assert_eq!( "hand,vs1\nAA,0.5,0.4".parse::<PreflopTable>().err(),
+            Some(ParseTableError::BadLine { line: 2, text: String::from("AA,0.5,0.4") }) );
+        assert_eq!( "hand,vs1\nAA,1.5".parse::<PreflopTable>().err(),
+            Some(ParseTableError::BadLine { line: 2, text: String::from("AA,1.5") }) );
+        assert_eq!( "hand,vs1\nAA,0.85".parse::<PreflopTable>().err(),
+            Some(ParseTableError::MissingHand("KK".parse().unwrap())) );
+
+        let table: PreflopTable = flat("0.5").parse().unwrap();
+        assert_eq!( table.equity( "32o".parse().unwrap(), 1 ), Some(0.5) );
+        assert!( PreflopTable::load( std::path::Path::new("/nonexistent/preflop.csv") ).is_err() );
+    }
+}
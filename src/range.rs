@@ -0,0 +1,385 @@
+use crate::card::{Card, CardRank, CardSuite, Hand};
+use strum::IntoEnumIterator;
+use std::fmt;
+use std::str::FromStr;
+
+/// One of the 169 kinds of starting hand: a pair, or two ranks either
+/// suited or offsuit. The higher rank always comes first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HandClass {
+    Pair(CardRank),
+    Suited(CardRank, CardRank),
+    Offsuit(CardRank, CardRank),
+}
+
+impl HandClass {
+    /// The class a two card hand belongs to, or None if it holds a joker.
+    pub fn of(hand: &Hand) -> Option<HandClass> {
+        assert!( hand.count() == 2 );
+        let (a, b) = (hand.card(0), hand.card(1));
+        if a.rank == CardRank::Joker || b.rank == CardRank::Joker {
+            return None;
+        }
+        let (high, low) = (a.rank.max(b.rank), a.rank.min(b.rank));
+        Some(if high == low {
+            HandClass::Pair(high)
+        } else if a.suite == b.suite {
+            HandClass::Suited(high, low)
+        } else {
+            HandClass::Offsuit(high, low)
+        })
+    }
+
+    /// Position in the 13 by 13 grid of starting hands, from 0 to 168:
+    /// pairs on the diagonal, suited hands with the higher rank as the
+    /// row and offsuit hands with it as the column.
+    pub fn index(&self) -> usize {
+        match self {
+            HandClass::Pair(r) => r.index() * 13 + r.index(),
+            HandClass::Suited(h, l) => h.index() * 13 + l.index(),
+            HandClass::Offsuit(h, l) => l.index() * 13 + h.index(),
+        }
+    }
+
+    /// All 169 classes, pairs first, from aces down.
+    pub fn all() -> Vec<HandClass> {
+        let ranks: Vec<CardRank> = CardRank::iter().rev().collect();
+        let mut v: Vec<HandClass> = ranks.iter().map(|r| HandClass::Pair(*r)).collect();
+        for (i, high) in ranks.iter().enumerate() {
+            for low in &ranks[i+1..] {
+                v.push( HandClass::Suited(*high, *low) );
+                v.push( HandClass::Offsuit(*high, *low) );
+            }
+        }
+        v
+    }
+
+    /// The 6 pairs, 4 suited or 12 offsuit hands in the class.
+    pub fn combos(&self) -> Vec<Hand> {
+        let suites: Vec<CardSuite> = CardSuite::iter().collect();
+        let mut v = Vec::new();
+        for (i, s1) in suites.iter().enumerate() {
+            for (j, s2) in suites.iter().enumerate() {
+                let (high, low) = match self {
+                    HandClass::Pair(r) if i < j => (r, r),
+                    HandClass::Suited(h, l) if i == j => (h, l),
+                    HandClass::Offsuit(h, l) if i != j => (h, l),
+                    _ => continue,
+                };
+                let mut hand = Hand::new();
+                hand.add( Card { rank: *high, suite: s1.clone() } );
+                hand.add( Card { rank: *low, suite: s2.clone() } );
+                v.push(hand);
+            }
+        }
+        v
+    }
+
+    /// The class in range notation, such as "QQ", "AKs" or "72o".
+    pub fn name(&self) -> String {
+        match self {
+            HandClass::Pair(r) => format!("{}{}", r.letter(), r.letter()),
+            HandClass::Suited(h, l) => format!("{}{}s", h.letter(), l.letter()),
+            HandClass::Offsuit(h, l) => format!("{}{}o", h.letter(), l.letter()),
+        }
+    }
+}
+
+impl fmt::Display for HandClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for HandClass {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_class(s) {
+            Some((high, low, suffix)) if high == low || suffix.is_some() => Ok(classes(high, low, suffix)[0]),
+            _ => Err(ParseRangeError::InvalidHand { token: s.to_string(), position: 0 }),
+        }
+    }
+}
+
+/// A two card hand in a range, with how often it is held: 1 for always,
+/// 0.5 for half the time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combo {
+    pub hand: Hand,
+    pub weight: f64,
+}
+
+/// Error returned when parsing a range. Positions are character offsets
+/// into the parsed string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangeError {
+    InvalidHand { token: String, position: usize },
+    InvalidWeight { token: String, position: usize },
+}
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRangeError::InvalidHand { token, position } =>
+                write!(f, "invalid hand '{}' at position {}", token, position),
+            ParseRangeError::InvalidWeight { token, position } =>
+                write!(f, "invalid weight in '{}' at position {}, expected more than 0 and at most 1", token, position),
+        }
+    }
+}
+
+impl std::error::Error for ParseRangeError {}
+
+/// A set of two card hands an opponent might hold, written in the usual
+/// range notation: "QQ+, AKs, A5s-A2s, KQo, 76s, AsKd, AA:0.5".
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Range {
+    combos: Vec<Combo>,
+}
+
+// Hands are kept with the higher rank first, and pairs in suite order, so
+// the same two cards always compare equal.
+fn normalize(hand: &Hand) -> Hand {
+    let mut cards = hand.cards().to_vec();
+    cards.sort_by_key(|c| (std::cmp::Reverse(c.rank), c.suite.index()));
+    let mut h = Hand::new();
+    for c in cards {
+        h.add(c);
+    }
+    h
+}
+
+impl Range {
+    pub fn new() -> Range {
+        Range { combos: Vec::new() }
+    }
+
+    /// Add a two card hand, replacing its weight if it is already in the range.
+    pub fn add(&mut self, hand: &Hand, weight: f64) {
+        assert!( hand.count() == 2 );
+        let hand = normalize(hand);
+        match self.combos.iter_mut().find(|c| c.hand == hand) {
+            Some(c) => c.weight = weight,
+            None => self.combos.push( Combo { hand, weight } ),
+        }
+    }
+
+    pub fn combos(&self) -> &[Combo] {
+        &self.combos
+    }
+
+    /// Number of combos, whatever their weight.
+    pub fn count(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Sum of the combos' weights.
+    pub fn total_weight(&self) -> f64 {
+        self.combos.iter().map(|c| c.weight).sum()
+    }
+
+    /// The hand's weight, or None if it is not in the range.
+    pub fn weight(&self, hand: &Hand) -> Option<f64> {
+        let hand = normalize(hand);
+        self.combos.iter().find(|c| c.hand == hand).map(|c| c.weight)
+    }
+
+    /// Drop the combos holding any of the dead cards, such as the board or
+    /// our own hole cards.
+    pub fn remove_blocked(&mut self, dead: &[Card]) {
+        self.combos.retain(|c| !c.hand.cards().iter().any(|card| dead.contains(card)));
+    }
+
+    // The weight shared by every combo of the class, if all are in the range.
+    fn class_weight(&self, class: HandClass) -> Option<f64> {
+        let weights: Vec<Option<f64>> = class.combos().iter().map(|h| self.weight(h)).collect();
+        match weights[0] {
+            Some(w) if weights.iter().all(|x| *x == Some(w)) => Some(w),
+            _ => None,
+        }
+    }
+}
+
+// A class written as two ranks and an optional s or o: "QQ", "AKs", "AK".
+fn parse_class(t: &str) -> Option<(CardRank, CardRank, Option<char>)> {
+    let chars: Vec<char> = t.chars().collect();
+    if chars.len() < 2 || chars.len() > 3 {
+        return None;
+    }
+    let rank = |c: char| CardRank::from_letter(c).filter(|r| *r != CardRank::Joker);
+    let (a, b) = (rank(chars[0])?, rank(chars[1])?);
+    let suffix = match chars.get(2).map(|c| c.to_ascii_lowercase()) {
+        None => None,
+        Some(c) if (c == 's' || c == 'o') && a != b => Some(c),
+        Some(_) => return None,
+    };
+    Some((a.max(b), a.min(b), suffix))
+}
+
+// The classes with ranks `high` and `low`: a pair, or the suited and
+// offsuit hands allowed by the suffix.
+fn classes(high: CardRank, low: CardRank, suffix: Option<char>) -> Vec<HandClass> {
+    if high == low {
+        return vec![HandClass::Pair(high)];
+    }
+    match suffix {
+        Some('s') => vec![HandClass::Suited(high, low)],
+        Some('o') => vec![HandClass::Offsuit(high, low)],
+        _ => vec![HandClass::Suited(high, low), HandClass::Offsuit(high, low)],
+    }
+}
+
+// Expand "QQ", "QQ+", "55-22", "AKs", "A2s+" or "A5s-A2s" into its classes.
+fn parse_classes(t: &str) -> Option<Vec<HandClass>> {
+    let between = |lo: CardRank, hi: CardRank| CardRank::iter().filter(move |r| *r >= lo.min(hi) && *r <= lo.max(hi));
+    if let Some((from, to)) = t.split_once('-') {
+        let (h1, l1, s1) = parse_class(from)?;
+        let (h2, l2, s2) = parse_class(to)?;
+        if h1 == l1 && h2 == l2 {
+            return Some( between(h1, h2).map(HandClass::Pair).collect() );
+        }
+        if h1 != h2 || h1 == l1 || h2 == l2 || s1 != s2 {
+            return None;
+        }
+        return Some( between(l1, l2).flat_map(|r| classes(h1, r, s1)).collect() );
+    }
+    if let Some(base) = t.strip_suffix('+') {
+        let (high, low, suffix) = parse_class(base)?;
+        // pairs go up to aces, other hands raise the kicker up to one
+        // below the high card
+        if high == low {
+            return Some( between(low, CardRank::Ace).map(HandClass::Pair).collect() );
+        }
+        return Some( between(low, high).filter(|r| *r != high).flat_map(|r| classes(high, r, suffix)).collect() );
+    }
+    let (high, low, suffix) = parse_class(t)?;
+    Some(classes(high, low, suffix))
+}
+
+// Split on commas and whitespace, keeping each token's character position.
+fn tokens(s: &str) -> Vec<(String, usize)> {
+    let mut v: Vec<(String, usize)> = Vec::new();
+    let mut current: Option<(String, usize)> = None;
+    for (i, c) in s.chars().enumerate() {
+        if c == ',' || c.is_whitespace() {
+            v.extend( current.take() );
+        } else {
+            current.get_or_insert_with(|| (String::new(), i)).0.push(c);
+        }
+    }
+    v.extend(current);
+    v
+}
+
+impl FromStr for Range {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::new();
+        for (token, position) in tokens(s) {
+            let (hands, weight) = match token.split_once(':') {
+                Some((hands, weight)) => match weight.parse::<f64>() {
+                    Ok(w) if w > 0.0 && w <= 1.0 => (hands, w),
+                    _ => return Err(ParseRangeError::InvalidWeight { token, position }),
+                },
+                None => (token.as_str(), 1.0),
+            };
+            let combos = match parse_classes(hands) {
+                Some(classes) => classes.iter().flat_map(|c| c.combos()).collect(),
+                // or two specific cards, such as "AsKd"
+                None => match hands.parse::<Hand>() {
+                    Ok(h) if h.count() == 2 && h.cards().iter().all(|c| c.rank != CardRank::Joker) => vec![h],
+                    _ => return Err(ParseRangeError::InvalidHand { token, position }),
+                },
+            };
+            for h in combos {
+                range.add(&h, weight);
+            }
+        }
+        Ok(range)
+    }
+}
+
+// Runs of consecutive entries sharing a weight, as (first, last, weight).
+fn runs(weights: &[Option<f64>]) -> Vec<(usize, usize, f64)> {
+    let mut v: Vec<(usize, usize, f64)> = Vec::new();
+    for (i, w) in weights.iter().enumerate() {
+        match (w, v.last_mut()) {
+            (Some(w), Some(run)) if run.1 + 1 == i && run.2 == *w => run.1 = i,
+            (Some(w), _) => v.push( (i, i, *w) ),
+            (None, _) => (),
+        }
+    }
+    v
+}
+
+fn with_weight(text: String, weight: f64) -> String {
+    if weight == 1.0 {
+        text
+    } else {
+        format!("{}:{}", text, weight)
+    }
+}
+
+// Written as compactly as the notation allows: whole classes are grouped
+// into runs such as "QQ+" and "A5s-A2s", suited and offsuit hands that go
+// together lose their suffix, and what is left of partly held classes is
+// listed card by card.
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ranks: Vec<CardRank> = CardRank::iter().rev().collect();
+        let mut parts: Vec<String> = Vec::new();
+
+        let pairs: Vec<Option<f64>> = ranks.iter().map(|r| self.class_weight(HandClass::Pair(*r))).collect();
+        for (i, j, w) in runs(&pairs) {
+            let (top, bottom) = (ranks[i].letter(), ranks[j].letter());
+            let text = match (i, j) {
+                _ if i == j => format!("{}{}", top, top),
+                (0, _) => format!("{}{}+", bottom, bottom),
+                _ => format!("{}{}-{}{}", top, top, bottom, bottom),
+            };
+            parts.push( with_weight(text, w) );
+        }
+
+        for (k, high) in ranks.iter().enumerate() {
+            let kickers = &ranks[k+1..];
+            let suited: Vec<Option<f64>> = kickers.iter().map(|l| self.class_weight(HandClass::Suited(*high, *l))).collect();
+            let offsuit: Vec<Option<f64>> = kickers.iter().map(|l| self.class_weight(HandClass::Offsuit(*high, *l))).collect();
+            let both: Vec<Option<f64>> = (0..kickers.len())
+                .map(|i| if suited[i] == offsuit[i] { suited[i] } else { None })
+                .collect();
+            let only = |v: &[Option<f64>]| -> Vec<Option<f64>> {
+                (0..v.len()).map(|i| if both[i].is_some() { None } else { v[i] }).collect()
+            };
+            for (suffix, weights) in [("", both.clone()), ("s", only(&suited)), ("o", only(&offsuit))] {
+                for (i, j, w) in runs(&weights) {
+                    let h = high.letter();
+                    let (top, bottom) = (kickers[i].letter(), kickers[j].letter());
+                    let text = match (i, j) {
+                        _ if i == j => format!("{}{}{}", h, top, suffix),
+                        (0, _) => format!("{}{}{}+", h, bottom, suffix),
+                        _ => format!("{}{}{}-{}{}{}", h, top, suffix, h, bottom, suffix),
+                    };
+                    parts.push( with_weight(text, w) );
+                }
+            }
+        }
+
+        for class in HandClass::all() {
+            if self.class_weight(class).is_some() {
+                continue;
+            }
+            for h in class.combos() {
+                if let Some(w) = self.weight(&h) {
+                    let text: String = h.cards().iter().flat_map(|c| [c.rank.letter(), c.suite.letter()]).collect();
+                    parts.push( with_weight(text, w) );
+                }
+            }
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests;
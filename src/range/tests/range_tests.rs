@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::range::*;
+    use crate::card::tests::support::hand;
+
+    fn range(s: &str) -> Range {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn hand_classes() {
+        let all = HandClass::all();
+        assert_eq!( all.len(), 169 );
+        assert_eq!( all.iter().map(|c| c.combos().len()).sum::<usize>(), 1326 );
+        assert_eq!( HandClass::of(&hand("Kd As")), Some(HandClass::Offsuit(CardRank::Ace, CardRank::King)) );
+        assert_eq!( HandClass::of(&hand("Kd As")).unwrap().name(), "AKo" );
+        assert_eq!( HandClass::of(&hand("7h 7c")).unwrap().to_string(), "77" );
+        assert_eq!( HandClass::of(&hand("Th 9h")).unwrap().to_string(), "T9s" );
+        for c in HandClass::all() {
+            assert!( c.combos().iter().all(|h| HandClass::of(h) == Some(c)) );
+        }
+        assert_eq!( HandClass::of(&hand("Xh As")), None );
+    }
+
+    #[test]
+    fn expand() {
+        assert_eq!( range("QQ+").count(), 18 );
+        assert_eq!( range("AKs").count(), 4 );
+        assert_eq!( range("AKo").count(), 12 );
+        assert_eq!( range("AK").count(), 16 );
+        assert_eq!( range("A5s-A2s").count(), 16 );
+        assert_eq!( range("A2s-A5s"), range("A5s-A2s") );
+        assert_eq!( range("55-22").count(), 24 );
+        assert_eq!( range("KTo+").count(), 36 );
+        assert_eq!( range("A2+").count(), 12 * 16 );
+        assert_eq!( range("22+").count(), 78 );
+        assert_eq!( range("QQ+, AKs, A5s-A2s, KQo, 76s").count(), 18 + 4 + 16 + 12 + 4 );
+
+        let r = range("AsKd, qq");
+        assert_eq!( r.count(), 7 );
+        assert_eq!( r.weight(&hand("Kd As")), Some(1.0) );
+        assert_eq!( r.weight(&hand("Ks Ad")), None );
+        assert_eq!( r.weight(&hand("Qc Qh")), Some(1.0) );
+        assert_eq!( range("").count(), 0 );
+    }
+
+    #[test]
+    fn weights() {
+        let r = range("AA:0.5, KK");
+        assert_eq!( r.count(), 12 );
+        assert_eq!( r.total_weight(), 9.0 );
+        assert_eq!( r.weight(&hand("As Ah")), Some(0.5) );
+        // a later mention of a hand replaces its weight
+        let r = range("QQ+, AA:0.25");
+        assert_eq!( r.weight(&hand("Ad Ac")), Some(0.25) );
+        assert_eq!( r.count(), 18 );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!( "QQ+, AKx".parse::<Range>().err(),
+            Some(ParseRangeError::InvalidHand { token: String::from("AKx"), position: 5 }) );
+        assert_eq!( "AA:1.5".parse::<Range>().err(),
+            Some(ParseRangeError::InvalidWeight { token: String::from("AA:1.5"), position: 0 }) );
+        assert_eq!( "KK AA:x".parse::<Range>().err(),
+            Some(ParseRangeError::InvalidWeight { token: String::from("AA:x"), position: 3 }) );
+        assert!( "AKs-QJs".parse::<Range>().is_err() );
+        assert!( "AKs-A2o".parse::<Range>().is_err() );
+        assert!( "AAs".parse::<Range>().is_err() );
+        assert!( "AsAs".parse::<Range>().is_err() );
+        assert!( "As".parse::<Range>().is_err() );
+        assert!( "XhAs".parse::<Range>().is_err() );
+    }
+
+    #[test]
+    fn blocked_combos() {
+        let mut r = range("AA, AKs");
+        r.remove_blocked( hand("As 2c").cards() );
+        assert_eq!( r.count(), 3 + 3 );
+        r.remove_blocked( hand("Kh Kd Kc").cards() );
+        assert_eq!( r.count(), 3 );
+        assert_eq!( r.to_string(), "AhAd, AhAc, AdAc" );
+    }
+
+    #[test]
+    fn compact_text() {
+        let s = "QQ+, AKs, A5s-A2s, KQo, 76s";
+        assert_eq!( range(s).to_string(), s );
+        assert_eq!( range("AKo, AKs, 22, 33, 44").to_string(), "44-22, AK" );
+        assert_eq!( range("KTs+, KTo+").to_string(), "KT+" );
+        assert_eq!( range("KQs, KJs, KTs, K9s").to_string(), "K9s+" );
+        assert_eq!( range("AA:0.5, KK, QQ").to_string(), "AA:0.5, KK-QQ" );
+        assert_eq!( range("AK, AKo:0.5").to_string(), "AKs, AKo:0.5" );
+        assert_eq!( range("AsKd, 9h8h:0.25").to_string(), "AsKd, 9h8h:0.25" );
+
+        let mut r = range("JJ");
+        r.remove_blocked( hand("Jc").cards() );
+        assert_eq!( r.to_string(), "JhJs, JhJd, JsJd" );
+
+        // every range reads back the same
+        for s in ["22+, A2s+, K9o+, 54s:0.5", "AA, KhKs, T9", "88-66, AJs-A9s, 32o"] {
+            let r = range(s);
+            let again = range( &r.to_string() );
+            assert_eq!( again.count(), r.count() );
+            assert!( r.combos().iter().all(|c| again.weight(&c.hand) == Some(c.weight)) );
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::card::{Card, CardRank, Hand, HandRank, HandWithData, combinations, low_value, value_name};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A set of rules for ranking 5 card hands against each other, so the same
+/// `Hand` can be judged as a high hand or as a lowball hand.
+pub trait Ranking {
+    /// A hand's strength under these rules. Better hands compare greater.
+    type Strength: Ord + fmt::Display;
+
+    fn name(&self) -> String;
+
+    fn strength(&self, hand: &Hand) -> Self::Strength;
+
+    /// The best 5 card hand from 5 or more cards, with its strength.
+    fn best_hand(&self, cards: &[Card]) -> (Hand, Self::Strength) {
+        assert!( cards.len() >= 5 );
+
+        let mut best: Option<(Hand, Self::Strength)> = None;
+        for c in combinations(cards.len(), 5) {
+            let mut h = Hand::new();
+            for i in c {
+                h.add( cards[i].clone() );
+            }
+            let s = self.strength(&h);
+            if best.as_ref().is_none_or(|(_, b)| s > *b) {
+                best = Some((h, s));
+            }
+        }
+        best.unwrap()
+    }
+}
+
+/// Ordinary high poker rankings, as used by hold'em.
+pub struct High;
+
+impl Ranking for High {
+    type Strength = HandWithData;
+
+    fn name(&self) -> String {
+        String::from("High")
+    }
+
+    fn strength(&self, hand: &Hand) -> HandWithData {
+        HandWithData::from_hand(hand)
+    }
+}
+
+/// Deuce-to-seven lowball: aces are always high, and straights and flushes
+/// count against the hand. The best hand is 7-5-4-3-2 of mixed suits.
+pub struct DeuceToSeven;
+
+impl Ranking for DeuceToSeven {
+    type Strength = LowballHand;
+
+    fn name(&self) -> String {
+        String::from("Deuce-to-Seven")
+    }
+
+    fn strength(&self, hand: &Hand) -> LowballHand {
+        LowballHand::new(hand, true, true)
+    }
+}
+
+/// Ace-to-five lowball: aces are always low, and straights and flushes are
+/// ignored. The best hand is 5-4-3-2-A, suited or not.
+pub struct AceToFive;
+
+impl Ranking for AceToFive {
+    type Strength = LowballHand;
+
+    fn name(&self) -> String {
+        String::from("Ace-to-Five")
+    }
+
+    fn strength(&self, hand: &Hand) -> LowballHand {
+        LowballHand::new(hand, false, false)
+    }
+}
+
+/// A hand judged for lowball. The lower hand is the better one and compares
+/// greater, as with `HandWithData`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LowballHand {
+    rank: HandRank,
+    // card values, the biggest group first, then highest first within
+    // groups of the same size
+    values: [u8; 5],
+}
+
+impl LowballHand {
+    // Group the cards by value, with the ace high or worth one as in an
+    // ace-to-five low, and find the hand's category, counting straights and
+    // flushes only if asked to.
+    fn new(hand: &Hand, ace_high: bool, straights_and_flushes: bool) -> LowballHand {
+        assert!( hand.count() == 5 );
+        assert!( hand.cards().iter().all(|c| c.rank != CardRank::Joker), "jokers are not ranked in lowball" );
+        let mut counts = [0u8; 15];
+        for c in hand.cards() {
+            let v = if ace_high && c.rank == CardRank::Ace { 14 } else { low_value(c.rank) };
+            counts[v as usize] += 1;
+        }
+        let mut groups: Vec<(u8, u8)> = (1..15u8)
+            .filter(|v| counts[*v as usize] > 0)
+            .map(|v| (counts[v as usize], v))
+            .collect();
+        groups.sort_by(|a, b| b.cmp(a));
+        let mut values = [0u8; 5];
+        let mut i = 0;
+        for (count, v) in &groups {
+            for _ in 0..*count {
+                values[i] = *v;
+                i += 1;
+            }
+        }
+
+        let rank = match (groups[0].0, groups.get(1).map_or(0, |g| g.0)) {
+            (4, _) => HandRank::FourOfAKind,
+            (3, 2) => HandRank::FullHouse,
+            (3, _) => HandRank::ThreeOfAKind,
+            (2, 2) => HandRank::TwoPair,
+            (2, _) => HandRank::Pair,
+            _ if !straights_and_flushes => HandRank::HighCard,
+            _ => {
+                let flush = hand.cards().iter().all(|c| c.suite == hand.card(0).suite);
+                let straight = values[0] - values[4] == 4;
+                match (straight, flush) {
+                    (true, true) => HandRank::StraightFlush,
+                    (false, true) => HandRank::Flush,
+                    (true, false) => HandRank::Straight,
+                    (false, false) => HandRank::HighCard,
+                }
+            }
+        };
+        LowballHand { rank, values }
+    }
+
+    /// The category the hand falls in, as for a high hand; in lowball a
+    /// lower category is better.
+    pub fn rank(&self) -> HandRank {
+        self.rank
+    }
+}
+
+impl Ord for LowballHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.rank, other.values).cmp(&(self.rank, self.values))
+    }
+}
+
+impl PartialOrd for LowballHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for LowballHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self.values.iter().map(|v| value_name(*v)).collect();
+        if self.rank == HandRank::HighCard {
+            write!(f, "{}", names.join("-"))
+        } else {
+            write!(f, "{}, {}", self.rank.name(), names.join("-"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::ranking::*;
+    use crate::card::tests::support::hand;
+    use strum::IntoEnumIterator;
+
+    // Every 5 card hand with its strength, strongest first.
+    fn all_hands<R: Ranking>(ranking: &R) -> Vec<(R::Strength, Hand)> {
+        let mut deck = Vec::new();
+        for suite in CardSuite::iter() {
+            for rank in CardRank::iter() {
+                deck.push( Card { rank, suite: suite.clone() } );
+            }
+        }
+        let mut res = Vec::new();
+        for c in combinations(deck.len(), 5) {
+            let mut h = Hand::new();
+            for i in c {
+                h.add( deck[i].clone() );
+            }
+            res.push( (ranking.strength(&h), h) );
+        }
+        res.sort_by(|a, b| b.0.cmp(&a.0));
+        res
+    }
+
+    fn classes<T: Eq>(hands: &[(T, Hand)]) -> usize {
+        1 + hands.windows(2).filter(|w| w[0].0 != w[1].0).count()
+    }
+
+    #[test]
+    fn deuce_to_seven_order() {
+        let r = DeuceToSeven;
+        assert!( r.strength(&hand("7c 5d 4h 3s 2c")) > r.strength(&hand("7c 6d 4h 3s 2c")) );
+        assert!( r.strength(&hand("8c 5d 4h 3s 2c")) < r.strength(&hand("7c 6d 5h 4s 2c")) );
+        // aces are high, so A-2-3-4-5 is only ace high and worse than any king high
+        assert_eq!( r.strength(&hand("Ac 2d 3h 4s 5c")).rank(), HandRank::HighCard );
+        assert!( r.strength(&hand("Ac 2d 3h 4s 5c")) < r.strength(&hand("Kc Qd Jh 9s 8c")) );
+        // straights and flushes count against the hand
+        assert_eq!( r.strength(&hand("6c 5d 4h 3s 2c")).rank(), HandRank::Straight );
+        assert_eq!( r.strength(&hand("7c 5c 4c 3c 2c")).rank(), HandRank::Flush );
+        assert!( r.strength(&hand("6c 5d 4h 3s 2c")) < r.strength(&hand("Ac Kd Qh Js 9c")) );
+        assert!( r.strength(&hand("2c 2d 3h 4s 5c")) < r.strength(&hand("Ac Kd Qh Js 9c")) );
+        assert_eq!( r.strength(&hand("7c 5d 4h 3s 2c")).to_string(), "7-5-4-3-2" );
+        assert_eq!( r.strength(&hand("Kc Kd 4h 3s 2c")).to_string(), "Pair, K-K-4-3-2" );
+    }
+
+    #[test]
+    fn ace_to_five_order() {
+        let r = AceToFive;
+        // the wheel is best, suited or not
+        assert_eq!( r.strength(&hand("5h 4h 3h 2h Ah")), r.strength(&hand("5c 4d 3h 2s As")) );
+        assert_eq!( r.strength(&hand("5h 4h 3h 2h Ah")).rank(), HandRank::HighCard );
+        assert!( r.strength(&hand("6c 4d 3h 2s Ac")) > r.strength(&hand("6c 5d 3h 2s Ac")) );
+        // aces are low, so a pair of aces is the best pair
+        assert!( r.strength(&hand("Ac Ad 2h 3s 4c")) > r.strength(&hand("2c 2d 3h 4s 5c")) );
+        assert!( r.strength(&hand("Kc Qd Jh 10s 9c")) > r.strength(&hand("Ac Ad 2h 3s 4c")) );
+        assert_eq!( r.strength(&hand("Ac Ad 2h 3s 4c")).to_string(), "Pair, A-A-4-3-2" );
+        assert_eq!( r.strength(&hand("5h 4h 3h 2h Ah")).to_string(), "5-4-3-2-A" );
+    }
+
+    #[test]
+    fn deuce_to_seven_exhaustive() {
+        let hands = all_hands(&DeuceToSeven);
+        assert_eq!( hands.len(), 2598960 );
+        // as many distinct hands as in high poker, just in another order
+        assert_eq!( classes(&hands), 7462 );
+        // best is 7-5-4-3-2 not all of one suit
+        let best = hands.iter().filter(|h| h.0 == hands[0].0).count();
+        assert_eq!( best, 4 * 4 * 4 * 4 * 4 - 4 );
+        assert_eq!( hands[0].0.to_string(), "7-5-4-3-2" );
+        // next best is 7-6-4-3-2
+        let second = hands.iter().find(|h| h.0 < hands[0].0).unwrap();
+        assert_eq!( second.0.to_string(), "7-6-4-3-2" );
+        // worst is a royal flush
+        let worst = &hands[hands.len() - 1];
+        assert_eq!( worst.0.rank(), HandRank::StraightFlush );
+        assert_eq!( worst.0.to_string(), "Straight Flush, A-K-Q-J-10" );
+        assert_eq!( hands.iter().filter(|h| h.0 == worst.0).count(), 4 );
+    }
+
+    #[test]
+    fn ace_to_five_exhaustive() {
+        let hands = all_hands(&AceToFive);
+        // one class for each multiset of ranks
+        assert_eq!( classes(&hands), 6175 );
+        // best is 5-4-3-2-A in any suits
+        assert_eq!( hands[0].0.to_string(), "5-4-3-2-A" );
+        assert_eq!( hands.iter().filter(|h| h.0 == hands[0].0).count(), 1024 );
+        let second = hands.iter().find(|h| h.0 < hands[0].0).unwrap();
+        assert_eq!( second.0.to_string(), "6-4-3-2-A" );
+        // worst is four kings with a queen
+        let worst = &hands[hands.len() - 1];
+        assert_eq!( worst.0.to_string(), "Four of a Kind, K-K-K-K-Q" );
+        assert_eq!( hands.iter().filter(|h| h.0 == worst.0).count(), 4 );
+        // no hand is a straight or flush
+        assert!( hands.iter().all(|h| h.0.rank() != HandRank::Flush && h.0.rank() != HandRank::Straight) );
+    }
+
+    #[test]
+    fn best_of_seven() {
+        // razz: the best A-5 low from seven cards
+        let cards = hand("Ac Ad 2h 3s 4c Kd Kh");
+        let (best, s) = AceToFive.best_hand( cards.cards() );
+        assert_eq!( s.to_string(), "K-4-3-2-A" );
+        assert_eq!( best.count(), 5 );
+        // paired boards force a pair
+        let cards = hand("Ac Ad 2h 2s 3c 3d 2c");
+        assert_eq!( AceToFive.best_hand( cards.cards() ).1.to_string(), "Two Pair, 2-2-A-A-3" );
+
+        let cards = hand("Ac 2d 3h 4s 5c 7d 6h");
+        assert_eq!( DeuceToSeven.best_hand( cards.cards() ).1.to_string(), "7-5-4-3-2" );
+        assert_eq!( High.best_hand( cards.cards() ).1.rank(), HandRank::Straight );
+    }
+
+    #[test]
+    #[should_panic(expected = "jokers are not ranked in lowball")]
+    fn lowball_rejects_jokers() {
+        AceToFive.strength( &hand("Xc 2d 3h 4s 5c") );
+    }
+}
@@ -0,0 +1,184 @@
+use crate::card::{Card, Deck, Hand, binomial, combinations};
+use crate::equity::{EquityError, WeightedPick, unseen_deck};
+use crate::eval::{CardSet, evaluate};
+use crate::range::Range;
+use rand::Rng;
+
+/// How good a hand is against one opponent, as in Billings' poker bots.
+/// `hs` is the chance of being ahead now, counting ties as half. `ppot` is
+/// the chance that a hand behind now is ahead by the river, and `npot` the
+/// chance that a hand ahead now falls behind.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HandStrength {
+    pub hs: f64,
+    pub ppot: f64,
+    pub npot: f64,
+    /// Whether every opponent hand and board was counted, rather than a sample.
+    pub exact: bool,
+}
+
+impl HandStrength {
+    /// Effective hand strength: the chance of being ahead now and staying
+    /// there, or being behind and drawing out.
+    pub fn ehs(&self) -> f64 {
+        self.hs * (1.0 - self.npot) + (1.0 - self.hs) * self.ppot
+    }
+
+    /// Hand strength against several opponents, taken as independent.
+    pub fn hs_against(&self, opponents: usize) -> f64 {
+        self.hs.powi(opponents as i32)
+    }
+}
+
+const AHEAD: usize = 0;
+const TIED: usize = 1;
+const BEHIND: usize = 2;
+
+fn outcome(mine: u16, theirs: u16) -> usize {
+    match mine.cmp(&theirs) {
+        std::cmp::Ordering::Greater => AHEAD,
+        std::cmp::Ordering::Equal => TIED,
+        std::cmp::Ordering::Less => BEHIND,
+    }
+}
+
+// Weighted counts of where the player stands now, and of where they stand
+// at the river given where they stood before.
+#[derive(Default)]
+struct Tally {
+    now: [f64; 3],
+    later: [[f64; 3]; 3],
+}
+
+impl Tally {
+    fn strength(&self, exact: bool) -> HandStrength {
+        let now: f64 = self.now.iter().sum();
+        let hs = if now > 0.0 { (self.now[AHEAD] + self.now[TIED] / 2.0) / now } else { 0.0 };
+        let total = |from: usize| self.later[from].iter().sum::<f64>();
+        let ratio = |x: f64, y: f64| if y > 0.0 { x / y } else { 0.0 };
+        let hp = &self.later;
+        let ppot = ratio( hp[BEHIND][AHEAD] + hp[BEHIND][TIED] / 2.0 + hp[TIED][AHEAD] / 2.0,
+                          total(BEHIND) + total(TIED) / 2.0 );
+        let npot = ratio( hp[AHEAD][BEHIND] + hp[TIED][BEHIND] / 2.0 + hp[AHEAD][TIED] / 2.0,
+                          total(AHEAD) + total(TIED) / 2.0 );
+        HandStrength { hs, ppot, npot, exact }
+    }
+}
+
+// The hands the opponent could hold, with their weights.
+type Hands = Vec<(CardSet, f64)>;
+
+// Check the deal and return the unseen cards and the opponent's hands:
+// every pair of unseen cards when no range is given. The opponent is
+// player 1 in errors.
+fn setup(hole: &Hand, board: &Hand, opponent: Option<&Range>) -> Result<(Vec<Card>, Hands), EquityError> {
+    if hole.count() != 2 {
+        return Err(EquityError::WrongHoleCards { player: 0, count: hole.count(), expected: 2 });
+    }
+    if !(3..=5).contains(&board.count()) {
+        return Err(EquityError::WrongBoard { count: board.count(), min: 3, max: 5 });
+    }
+    let deck = unseen_deck( Deck::new(), [hole, board].into_iter() )?;
+    let rest = deck.cards().to_vec();
+    let hands: Hands = match opponent {
+        Some(range) => {
+            let mut range = range.clone();
+            range.remove_blocked( &[hole.cards(), board.cards()].concat() );
+            range.combos().iter().map(|c| (CardSet::from_hand(&c.hand), c.weight)).collect()
+        }
+        None => combinations(rest.len(), 2).iter()
+            .map(|c| (CardSet::from_cards(&[rest[c[0]].clone(), rest[c[1]].clone()]), 1.0))
+            .collect(),
+    };
+    if hands.is_empty() {
+        return Err(EquityError::EmptyRange(1));
+    }
+    Ok((rest, hands))
+}
+
+/// Work out hand strength and potential exactly, against every hand the
+/// opponent could hold and every way the board could finish. The opponent
+/// holds any two unseen cards, or a hand from `opponent` by its weight. On
+/// the river there is no potential left, so both are zero.
+pub fn exact(hole: &Hand, board: &Hand, opponent: Option<&Range>) -> Result<HandStrength, EquityError> {
+    let (rest, hands) = setup(hole, board, opponent)?;
+    Ok(count_exact(hole, board, &rest, &hands))
+}
+
+// `exact`, given what `setup` found.
+fn count_exact(hole: &Hand, board: &Hand, rest: &[Card], hands: &Hands) -> HandStrength {
+    let board_set = CardSet::from_hand(board);
+    let mine = board_set.union( CardSet::from_hand(hole) );
+    let me = evaluate(mine);
+    let before: Vec<usize> = hands.iter().map(|(h, _)| outcome( me, evaluate(h.union(board_set)) )).collect();
+
+    let mut tally = Tally::default();
+    for ((_, weight), from) in hands.iter().zip(&before) {
+        tally.now[*from] += weight;
+    }
+    if board.count() < 5 {
+        for c in combinations(rest.len(), 5 - board.count()) {
+            let runout = CardSet::from_cards( &c.iter().map(|i| rest[*i].clone()).collect::<Vec<Card>>() );
+            let me = evaluate( mine.union(runout) );
+            let common = board_set.union(runout);
+            for ((h, weight), from) in hands.iter().zip(&before) {
+                if !h.intersects(runout) {
+                    tally.later[*from][outcome( me, evaluate(h.union(common)) )] += weight;
+                }
+            }
+        }
+    }
+    tally.strength(true)
+}
+
+/// Estimate hand strength and potential from `trials` random deals of an
+/// opponent hand, chosen as in `exact`, and the rest of the board.
+pub fn sampled<R: Rng + ?Sized>(hole: &Hand, board: &Hand, opponent: Option<&Range>,
+                                trials: u32, rng: &mut R) -> Result<HandStrength, EquityError> {
+    let (rest, hands) = setup(hole, board, opponent)?;
+    Ok(count_sampled(hole, board, &rest, &hands, trials, rng))
+}
+
+// `sampled`, given what `setup` found.
+fn count_sampled<R: Rng + ?Sized>(hole: &Hand, board: &Hand, rest: &[Card], hands: &Hands,
+                                  trials: u32, rng: &mut R) -> HandStrength {
+    let board_set = CardSet::from_hand(board);
+    let mine = board_set.union( CardSet::from_hand(hole) );
+    let me = evaluate(mine);
+    let pick = WeightedPick::new( hands.iter().map(|(_, w)| *w) );
+
+    let mut tally = Tally::default();
+    for _ in 0..trials {
+        let theirs = hands[pick.pick(rng)].0;
+        let from = outcome( me, evaluate(theirs.union(board_set)) );
+        tally.now[from] += 1.0;
+
+        let mut common = board_set;
+        while common.count() < 5 {
+            let c = &rest[rng.gen_range(0..rest.len())];
+            if !theirs.contains(c) {
+                common.insert(c);
+            }
+        }
+        if board.count() < 5 {
+            tally.later[from][outcome( evaluate(mine.union(common)), evaluate(theirs.union(common)) )] += 1.0;
+        }
+    }
+    tally.strength(false)
+}
+
+/// Work out the metrics exactly when that takes no more than `trials`
+/// opponent hands and boards, otherwise estimate them from `trials` deals.
+pub fn calculate<R: Rng + ?Sized>(hole: &Hand, board: &Hand, opponent: Option<&Range>,
+                                  trials: u32, rng: &mut R) -> Result<HandStrength, EquityError> {
+    let (rest, hands) = setup(hole, board, opponent)?;
+    let boards = binomial(rest.len() as u64, 5 - board.count() as u64);
+    if boards * hands.len() as u64 <= trials as u64 {
+        Ok(count_exact(hole, board, &rest, &hands))
+    } else {
+        Ok(count_sampled(hole, board, &rest, &hands, trials, rng))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1 @@
+pub mod strength_tests;
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use crate::equity::EquityError;
+    use crate::range::Range;
+    use crate::strength::*;
+    use crate::card::tests::support::hand;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn billings_example() {
+        // the worked example from Billings et al., "The challenge of poker"
+        let s = exact( &hand("Ad Qc"), &hand("3h 4c Jh"), None ).unwrap();
+        assert!( s.exact );
+        assert!( (s.hs - 0.585).abs() < 0.0005 );
+        assert!( (s.ppot - 0.208).abs() < 0.0005 );
+        assert!( (s.npot - 0.274).abs() < 0.0005 );
+        assert!( (s.ehs() - (s.hs * (1.0 - s.npot) + (1.0 - s.hs) * s.ppot)).abs() < 1e-12 );
+        assert!( (s.hs_against(2) - s.hs * s.hs).abs() < 1e-12 );
+    }
+
+    #[test]
+    fn river_has_no_potential() {
+        let s = exact( &hand("As Ks"), &hand("Qs Js Ts 2c 3d"), None ).unwrap();
+        assert_eq!( (s.hs, s.ppot, s.npot, s.ehs()), (1.0, 0.0, 0.0, 1.0) );
+        // the board plays for everyone
+        let s = exact( &hand("2h 3h"), &hand("Ac Kd Qs Jh Tc"), None ).unwrap();
+        assert_eq!( s.hs, 0.5 );
+    }
+
+    #[test]
+    fn against_a_range() {
+        let aces: Range = "AA".parse().unwrap();
+        let s = exact( &hand("Kc Kd"), &hand("7c 2d 3s"), Some(&aces) ).unwrap();
+        assert_eq!( s.hs, 0.0 );
+        assert_eq!( s.npot, 0.0 );
+        // a king on the turn or river, less the runner runner straights
+        // and flushes that beat both
+        assert!( s.ppot > 0.08 && s.ppot < 0.09 );
+
+        let blocked: Range = "AsAh".parse().unwrap();
+        assert_eq!( exact( &hand("As Kd"), &hand("7c 2d 3s"), Some(&blocked) ), Err(EquityError::EmptyRange(1)) );
+    }
+
+    #[test]
+    fn sampled_close_to_exact() {
+        let hole = hand("Ah Kh");
+        let board = hand("Qh 7h 2c 3d");
+        let e = exact( &hole, &board, None ).unwrap();
+        let mut rng = StdRng::seed_from_u64(25);
+        let s = sampled( &hole, &board, None, 40000, &mut rng ).unwrap();
+        assert!( !s.exact );
+        assert!( (s.hs - e.hs).abs() < 0.01 );
+        assert!( (s.ppot - e.ppot).abs() < 0.01 );
+        assert!( (s.npot - e.npot).abs() < 0.01 );
+
+        // a turn against random hands is small enough to count in full
+        let c = calculate( &hole, &board, None, 100000, &mut rng ).unwrap();
+        assert_eq!( c, e );
+        assert!( !calculate( &hole, &board, None, 1000, &mut rng ).unwrap().exact );
+    }
+
+    #[test]
+    fn bad_deals() {
+        assert_eq!( exact( &hand("Ah"), &hand("Qh 7h 2c"), None ), Err(EquityError::WrongHoleCards { player: 0, count: 1, expected: 2 }) );
+        assert_eq!( exact( &hand("Ah Kh"), &hand("Qh 7h"), None ),
+            Err(EquityError::WrongBoard { count: 2, min: 3, max: 5 }) );
+        assert_eq!( exact( &hand("Ah Kh"), &hand("Qh 7h Ah"), None ),
+            Err(EquityError::DuplicateCard(hand("Ah").cards()[0].clone())) );
+        assert_eq!( exact( &hand("Ah Xh"), &hand("Qh 7h 2c"), None ),
+            Err(EquityError::NotInDeck(hand("Xh").cards()[0].clone())) );
+    }
+}
@@ -0,0 +1,269 @@
+use crate::card::{Card, CardRank, Deck, EIGHT_OR_BETTER, HandWithData, LowHand, find_best_hand, find_best_low, winners};
+use crate::ranking::{AceToFive, LowballHand, Ranking};
+use std::fmt;
+use std::str::FromStr;
+
+/// The seven card stud games.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum StudGame {
+    #[default]
+    Stud,
+    // split with the best eight-or-better low
+    StudHiLo,
+    // ace-to-five lowball
+    Razz,
+}
+
+impl StudGame {
+    pub fn name(&self) -> String {
+        match self {
+            StudGame::Stud     => String::from("Seven Card Stud"),
+            StudGame::StudHiLo => String::from("Seven Card Stud Hi-Lo"),
+            StudGame::Razz     => String::from("Razz"),
+        }
+    }
+}
+
+impl fmt::Display for StudGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStudGameError(pub String);
+
+impl fmt::Display for ParseStudGameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown stud game '{}', expected stud, stud-hilo or razz", self.0)
+    }
+}
+
+impl std::error::Error for ParseStudGameError {}
+
+impl FromStr for StudGame {
+    type Err = ParseStudGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "stud" => Ok(StudGame::Stud),
+            "stud-hilo" | "stud8" => Ok(StudGame::StudHiLo),
+            "razz" => Ok(StudGame::Razz),
+            _ => Err(ParseStudGameError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StudError {
+    TooFewPlayers,
+    TooManyPlayers,
+}
+
+impl fmt::Display for StudError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StudError::TooFewPlayers => write!(f, "at least two players are needed"),
+            StudError::TooManyPlayers => write!(f, "at most eight players can play stud from one deck"),
+        }
+    }
+}
+
+impl std::error::Error for StudError {}
+
+/// One player's cards, face down and face up, in the order dealt.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StudHand {
+    pub down: Vec<Card>,
+    pub up: Vec<Card>,
+}
+
+impl StudHand {
+    /// The first face up card, dealt on third street.
+    pub fn door_card(&self) -> &Card {
+        &self.up[0]
+    }
+
+    pub fn count(&self) -> usize {
+        self.down.len() + self.up.len()
+    }
+
+    /// The cards other players can see, with the down cards hidden.
+    pub fn exposed(&self) -> String {
+        let mut names = vec![String::from("??"); self.down.len()];
+        names.extend( self.up.iter().map(|c| c.name()) );
+        names.join(" ")
+    }
+}
+
+// The dealer's view: down cards in brackets, then the up cards.
+impl fmt::Display for StudHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names = |cards: &[Card]| cards.iter().map(|c| c.name()).collect::<Vec<String>>().join(" ");
+        // the last down card comes on seventh street
+        let (first, last) = self.down.split_at(self.down.len().min(2));
+        write!(f, "[{}] {}", names(first), names(&self.up))?;
+        if !last.is_empty() {
+            write!(f, " [{}]", names(last))?;
+        }
+        Ok(())
+    }
+}
+
+/// Who won at showdown. Stud pays only the high hand and razz only the
+/// low; the split game may have no qualifying low, when the high takes all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Showdown {
+    High(Vec<usize>),
+    Low(Vec<usize>),
+    HiLo { high: Vec<usize>, low: Option<Vec<usize>> },
+}
+
+/// A seven card stud deal, from third street to seventh.
+pub struct StudDeal {
+    game: StudGame,
+    hands: Vec<StudHand>,
+    // dealt face up for everyone when seventh street would run out the deck
+    common: Option<Card>,
+    deck: Deck,
+    street: u8,
+}
+
+impl StudDeal {
+    /// Deal third street, two cards down and one up to each player, from
+    /// a full, shuffled deck.
+    pub fn new(game: StudGame, players: usize, deck: Deck) -> Result<StudDeal, StudError> {
+        if players < 2 {
+            return Err(StudError::TooFewPlayers);
+        }
+        if players > 8 {
+            return Err(StudError::TooManyPlayers);
+        }
+        let mut d = StudDeal { game, hands: vec![StudHand::default(); players], common: None, deck, street: 3 };
+        for face_up in [false, false, true] {
+            d.deal_round(face_up);
+        }
+        Ok(d)
+    }
+
+    // One card to each player in turn.
+    fn deal_round(&mut self, face_up: bool) {
+        for h in self.hands.iter_mut() {
+            let card = self.deck.draw().expect("deck ran out dealing stud");
+            if face_up {
+                h.up.push(card);
+            } else {
+                h.down.push(card);
+            }
+        }
+    }
+
+    pub fn game(&self) -> StudGame {
+        self.game
+    }
+
+    /// The street last dealt, from 3 to 7.
+    pub fn street(&self) -> u8 {
+        self.street
+    }
+
+    pub fn hands(&self) -> &[StudHand] {
+        &self.hands
+    }
+
+    /// The card shared by everyone on seventh street, if the deck was too
+    /// short to give each player their own.
+    pub fn common(&self) -> Option<&Card> {
+        self.common.as_ref()
+    }
+
+    /// Deal the next street: one card up on fourth to sixth street, one
+    /// down on seventh. Returns false once seventh street has been dealt.
+    pub fn next_street(&mut self) -> bool {
+        match self.street {
+            3..=5 => self.deal_round(true),
+            6 if self.deck.count() < self.hands.len() => self.common = self.deck.draw(),
+            6 => self.deal_round(false),
+            _ => return false,
+        }
+        self.street += 1;
+        true
+    }
+
+    /// The player who must bring in on third street: the lowest door card,
+    /// with ties going to the lowest suit. In razz it is the highest door
+    /// card, aces counting low, with ties going to the highest suit.
+    pub fn bring_in(&self) -> usize {
+        let seats = 0..self.hands.len();
+        let door = |i: &usize| self.hands[*i].door_card();
+        match self.game {
+            StudGame::Razz => seats.max_by_key(|i| {
+                let c = door(i);
+                let value = if c.rank == CardRank::Ace { 0 } else { c.rank.index() + 1 };
+                (value, c.suite.bridge_order())
+            }),
+            _ => seats.min_by_key(|i| {
+                let c = door(i);
+                (c.rank.index(), c.suite.bridge_order())
+            }),
+        }.unwrap()
+    }
+
+    /// All the cards a player can use: their own and any common card.
+    pub fn cards(&self, player: usize) -> Vec<Card> {
+        let h = &self.hands[player];
+        h.down.iter().chain(&h.up).chain(&self.common).cloned().collect()
+    }
+
+    fn high(&self, player: usize) -> HandWithData {
+        find_best_hand( &self.cards(player), false ).data
+    }
+
+    fn low(&self, player: usize) -> Option<LowHand> {
+        find_best_low( &self.cards(player), EIGHT_OR_BETTER ).map(|b| b.data)
+    }
+
+    fn razz(&self, player: usize) -> LowballHand {
+        AceToFive.best_hand( &self.cards(player) ).1
+    }
+
+    /// The player's best hand for this game, e.g. for showdown output.
+    pub fn describe(&self, player: usize) -> String {
+        match self.game {
+            StudGame::Stud => self.high(player).to_string().trim_start().to_string(),
+            StudGame::StudHiLo => {
+                let low = match self.low(player) {
+                    Some(low) => format!("low {}", low),
+                    None => String::from("no low"),
+                };
+                format!("{}; {}", self.high(player).to_string().trim_start(), low)
+            }
+            StudGame::Razz => self.razz(player).to_string(),
+        }
+    }
+
+    /// Compare everyone's best hands once seventh street is dealt.
+    pub fn showdown(&self) -> Showdown {
+        assert!( self.street == 7 );
+        let players = 0..self.hands.len();
+        match self.game {
+            StudGame::Stud => {
+                let highs: Vec<HandWithData> = players.map(|i| self.high(i)).collect();
+                Showdown::High(winners(&highs))
+            }
+            StudGame::StudHiLo => {
+                let highs: Vec<HandWithData> = players.clone().map(|i| self.high(i)).collect();
+                let lows: Vec<Option<LowHand>> = players.map(|i| self.low(i)).collect();
+                let low = if lows.iter().any(|l| l.is_some()) { Some(winners(&lows)) } else { None };
+                Showdown::HiLo { high: winners(&highs), low }
+            }
+            StudGame::Razz => {
+                let lows: Vec<LowballHand> = players.map(|i| self.razz(i)).collect();
+                Showdown::Low(winners(&lows))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
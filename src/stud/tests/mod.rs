@@ -0,0 +1 @@
+pub mod stud_tests;
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+    use crate::stud::*;
+
+    // Two players: two down cards each, then door cards `p0` and `p1`.
+    fn doors(p0: &str, p1: &str) -> Deck {
+        format!("Qh 9c Jh 10c {} {} 8h 7c 6h 5c 4h 4c 6d 7d", p0, p1).parse().unwrap()
+    }
+
+    fn deal_out(game: StudGame, deck: &str) -> StudDeal {
+        let mut d = StudDeal::new( game, 2, deck.parse().unwrap() ).unwrap();
+        while d.next_street() {}
+        d
+    }
+
+    #[test]
+    fn streets() {
+        let mut d = StudDeal::new( StudGame::Stud, 4, Deck::new_seeded(1) ).unwrap();
+        assert_eq!( d.street(), 3 );
+        for h in d.hands() {
+            assert_eq!( (h.down.len(), h.up.len()), (2, 1) );
+            assert_eq!( h.exposed(), format!("?? ?? {}", h.door_card()) );
+        }
+        for street in 4..=7 {
+            assert!( d.next_street() );
+            assert_eq!( d.street(), street );
+        }
+        assert!( !d.next_street() );
+        for h in d.hands() {
+            assert_eq!( (h.down.len(), h.up.len()), (3, 4) );
+            assert!( h.to_string().starts_with('[') && h.to_string().ends_with(']') );
+        }
+        assert!( d.common().is_none() );
+
+        assert_eq!( StudDeal::new( StudGame::Stud, 1, Deck::new() ).err(), Some(StudError::TooFewPlayers) );
+        assert_eq!( StudDeal::new( StudGame::Stud, 9, Deck::new() ).err(), Some(StudError::TooManyPlayers) );
+    }
+
+    #[test]
+    fn common_card_for_eight() {
+        // 8 players use 48 cards by sixth street, leaving too few for seventh
+        let mut d = StudDeal::new( StudGame::Stud, 8, Deck::new_seeded(2) ).unwrap();
+        while d.next_street() {}
+        let common = d.common().unwrap().clone();
+        assert!( d.hands().iter().all(|h| h.count() == 6) );
+        assert_eq!( d.cards(3).len(), 7 );
+        assert!( d.cards(3).contains(&common) );
+    }
+
+    #[test]
+    fn display() {
+        let d = deal_out( StudGame::Stud, "As Kc 2s Kd 3s Kh 4s Qc 5s Qd 9h Jc 9d Jd" );
+        assert_eq!( d.hands()[0].to_string(), "[A\u{2660} 2\u{2660}] 3\u{2660} 4\u{2660} 5\u{2660} 9\u{2665} [9\u{2666}]" );
+        assert_eq!( d.hands()[1].exposed(), "?? ?? ?? K\u{2665} Q\u{2663} Q\u{2666} J\u{2663}" );
+        assert_eq!( d.hands()[1].door_card().to_string(), "K\u{2665}" );
+    }
+
+    #[test]
+    fn bring_in() {
+        let d = StudDeal::new( StudGame::Stud, 2, doors("3s", "Kh") ).unwrap();
+        assert_eq!( d.bring_in(), 0 );
+        // equal ranks go by suit, clubs lowest
+        let d = StudDeal::new( StudGame::Stud, 2, doors("2d", "2c") ).unwrap();
+        assert_eq!( d.bring_in(), 1 );
+        let d = StudDeal::new( StudGame::StudHiLo, 2, doors("2h", "2s") ).unwrap();
+        assert_eq!( d.bring_in(), 0 );
+        // aces are high in stud
+        let d = StudDeal::new( StudGame::Stud, 2, doors("As", "3c") ).unwrap();
+        assert_eq!( d.bring_in(), 1 );
+
+        // razz brings in with the highest card, aces low, spades highest
+        let d = StudDeal::new( StudGame::Razz, 2, doors("3s", "Kh") ).unwrap();
+        assert_eq!( d.bring_in(), 1 );
+        let d = StudDeal::new( StudGame::Razz, 2, doors("As", "2c") ).unwrap();
+        assert_eq!( d.bring_in(), 1 );
+        let d = StudDeal::new( StudGame::Razz, 2, doors("Ks", "Kd") ).unwrap();
+        assert_eq!( d.bring_in(), 0 );
+
+        assert_eq!( CardSuite::Clubs.bridge_order(), 0 );
+        assert_eq!( CardSuite::Spades.bridge_order(), 3 );
+    }
+
+    #[test]
+    fn showdowns() {
+        // player 1 has a steel wheel, player 2 kings full
+        let deck = "As Kc 2s Kd 3s Kh 4s Qc 5s Qd 9h Jc 9d Jd";
+        let d = deal_out( StudGame::Stud, deck );
+        assert_eq!( d.showdown(), Showdown::High(vec![0]) );
+        assert!( d.describe(0).starts_with("Straight Flush") );
+        let d = deal_out( StudGame::StudHiLo, deck );
+        assert_eq!( d.showdown(), Showdown::HiLo { high: vec![0], low: Some(vec![0]) } );
+        assert!( d.describe(0).ends_with("low 5-4-3-2-A") );
+        assert!( d.describe(1).ends_with("no low") );
+        let d = deal_out( StudGame::Razz, deck );
+        assert_eq!( d.showdown(), Showdown::Low(vec![0]) );
+        assert_eq!( d.describe(0), "5-4-3-2-A" );
+
+        // nobody makes an eight-or-better low
+        let deck = "As Kc Ks Kd 3s Kh 4s Qc 5s Qd 9h Jc 9d Jd";
+        let d = deal_out( StudGame::StudHiLo, deck );
+        assert_eq!( d.showdown(), Showdown::HiLo { high: vec![1], low: None } );
+        // but razz always has a low
+        let d = deal_out( StudGame::Razz, deck );
+        assert_eq!( d.showdown(), Showdown::Low(vec![0]) );
+        assert_eq!( d.describe(1), "Two Pair, Q-Q-J-J-K" );
+    }
+
+    #[test]
+    fn parse_games() {
+        assert_eq!( "razz".parse::<StudGame>(), Ok(StudGame::Razz) );
+        assert_eq!( "stud8".parse::<StudGame>(), Ok(StudGame::StudHiLo) );
+        assert!( "holdem".parse::<StudGame>().is_err() );
+    }
+}